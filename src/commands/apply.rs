@@ -0,0 +1,24 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::manifest::handle_apply;
+use crate::options::Options;
+
+/// Install, remove and set default binaries to match a manifest, without prompting.
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Manifest describing the desired binaries
+    #[arg(short, long, default_value = "tools.toml")]
+    file: PathBuf,
+}
+
+impl Command {
+    pub async fn exec(&self, options: &Options) -> Result<()> {
+        handle_apply(&self.file, options).await
+    }
+}
@@ -2,6 +2,7 @@ use anyhow::Result;
 use clap::Args;
 
 use crate::handle_commands::handle_cmd;
+use crate::options::Options;
 
 use super::ComponentCommands;
 
@@ -22,14 +23,14 @@ pub struct Command {
 }
 
 impl Command {
-    pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
+    pub async fn exec(&self, options: &Options) -> Result<()> {
         handle_cmd(
             ComponentCommands::Cleanup {
                 all: self.all,
                 days: self.days,
                 dry_run: self.dry_run,
             },
-            github_token.to_owned(),
+            options,
         )
         .await
     }
@@ -7,13 +7,14 @@ use tracing::{debug, info};
 
 use crate::{
     commands::{parse_component_with_version, BinaryName, CommandMetadata},
-    handlers::{installed_binaries_grouped_by_network, update_default_version_file},
-    paths::{binaries_dir, get_default_bin_dir},
+    handlers::{
+        installed_binaries_grouped_by_network, installed_binary_path, shim::install_shim,
+        sui_config::handle_sync_env, update_default_version_file,
+    },
+    paths::get_default_bin_dir,
+    types::BinaryVersion,
 };
 
-#[cfg(not(windows))]
-use std::os::unix::fs::PermissionsExt;
-
 /// Set the default Sui CLI version.
 #[derive(Args, Debug)]
 pub struct Command {
@@ -31,6 +32,11 @@ pub struct Command {
     /// Use `suiup show` to find all installed binaries
     #[arg(long, value_name = "branch", default_missing_value = "main", num_args = 0..=1)]
     nightly: Option<String>,
+
+    /// Also make the sui client env matching the network active (edits client.yaml in
+    /// $SUI_CONFIG_DIR or ~/.sui/sui_config, keeping a backup)
+    #[arg(long)]
+    sync_env: bool,
 }
 
 impl Command {
@@ -39,6 +45,7 @@ impl Command {
             name,
             debug,
             nightly,
+            sync_env,
         } = self;
 
         if name.is_empty() && nightly.is_none() {
@@ -104,8 +111,17 @@ impl Command {
             anyhow!("Binary {binary_version} from {network} release not found. Use `suiup show` to see installed binaries.")
         })?;
 
-        // copy files to default-bin
+        // place the shim for this binary in default-bin
         let mut dst = get_default_bin_dir();
+        let binary = BinaryVersion {
+            binary_name: name.to_string(),
+            network_release: network.to_string(),
+            version: version.clone(),
+            debug: *debug,
+            path: None,
+            sha256: None,
+            provenance: None,
+        };
         let name = if *debug {
             format!("{}-debug", name)
         } else {
@@ -117,52 +133,16 @@ impl Command {
         #[cfg(target_os = "windows")]
         dst.set_extension("exe");
 
-        let mut src = binaries_dir();
-        src.push(network);
-
-        if nightly.is_some() {
-            // cargo install adds a bin folder to the specified path :-)
-            src.push("bin");
-        }
-
-        if *debug {
-            src.push(format!("{}-debug-{}", name, version));
-        } else {
-            src.push(binary_version);
-        }
-
+        let src = installed_binary_path(&binary);
         info!("File source: {}", src.display());
-
-        #[cfg(target_os = "windows")]
-        let filename = src.file_name().expect("Expected binary filename");
-        #[cfg(target_os = "windows")]
-        src.set_file_name(format!(
-            "{}.exe",
-            filename
-                .to_str()
-                .expect("Expected binary filename as string")
-        ));
-
-        #[cfg(not(target_os = "windows"))]
-        {
-            if dst.exists() {
-                std::fs::remove_file(&dst)?;
-            }
-
-            std::fs::copy(&src, &dst)?;
-
-            #[cfg(unix)]
-            {
-                let mut perms = std::fs::metadata(&dst)?.permissions();
-                perms.set_mode(0o755);
-                std::fs::set_permissions(&dst, perms)?;
-            }
+        if !src.exists() {
+            bail!(
+                "Binary {} not found. Use `suiup show` to see installed binaries.",
+                src.display()
+            );
         }
 
-        #[cfg(target_os = "windows")]
-        {
-            std::fs::copy(&src, &dst)?;
-        }
+        install_shim(&dst)?;
 
         update_default_version_file(
             &vec![name.to_string()],
@@ -172,6 +152,10 @@ impl Command {
         )?;
 
         println!("Default binary updated successfully");
+
+        if *sync_env {
+            handle_sync_env(network)?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,37 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+
+use crate::handlers::profile::handle_env;
+
+#[derive(Clone, Debug, ValueEnum)]
+enum Shell {
+    Sh,
+    Fish,
+    Powershell,
+}
+
+/// Print shell commands that set SUI_CONFIG_DIR to a profile's sui config.
+/// e.g. `eval "$(suiup env)"`
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Profile to use. Defaults to the active profile
+    profile: Option<String>,
+
+    /// Shell syntax to print
+    #[arg(long, value_enum, default_value = "sh")]
+    shell: Shell,
+}
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        let shell = match self.shell {
+            Shell::Sh => "sh",
+            Shell::Fish => "fish",
+            Shell::Powershell => "powershell",
+        };
+        handle_env(self.profile.clone(), shell)
+    }
+}
@@ -1,19 +1,67 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
 use clap::Args;
 
 use crate::handle_commands::handle_cmd;
+use crate::handlers::install::{install_from_archive, ArchiveOrigin};
+use crate::handlers::lockfile::install_locked;
+use crate::handlers::move_lock::install_from_move_lock;
+use crate::options::Options;
 
 use super::ComponentCommands;
 
 /// Install a binary.
 #[derive(Args, Debug)]
 pub struct Command {
-    /// Binary to install with optional version
-    /// (e.g. 'sui', 'sui@1.40.1', 'sui@testnet', 'sui@testnet-1.39.3')
-    component: String,
+    /// Binaries to install with optional version
+    /// (e.g. 'sui', 'sui@1.40.1', 'sui@testnet', 'sui@testnet-1.39.3').
+    /// Several binaries are downloaded concurrently (e.g. 'sui@testnet walrus@testnet mvr')
+    #[arg(
+        id = "component",
+        value_name = "COMPONENT",
+        required_unless_present_any = ["from_move_lock", "locked"]
+    )]
+    components: Vec<String>,
+
+    /// Install the sui release matching the compiler version recorded in a package's Move.lock.
+    /// Takes the package directory or the Move.lock path (defaults to the current directory).
+    #[arg(
+        long,
+        value_name = "path",
+        default_missing_value = ".",
+        num_args = 0..=1,
+        conflicts_with_all = ["component", "nightly", "debug"]
+    )]
+    from_move_lock: Option<PathBuf>,
+
+    /// Install the exact assets recorded in the nearest suiup.lock, refusing any whose SHA-256
+    /// differs. Without a binary, everything in the lock file is installed.
+    #[arg(long, conflicts_with_all = ["from_move_lock", "nightly"])]
+    locked: bool,
+
+    /// Install from a local release archive instead of the published releases. The network and
+    /// version are derived from the file name, e.g. sui-testnet-v1.40.1-ubuntu-x86_64.tgz.
+    #[arg(
+        long,
+        value_name = "path",
+        requires = "component",
+        conflicts_with_all = ["from_url", "from_move_lock", "locked", "nightly"]
+    )]
+    from_file: Option<PathBuf>,
+
+    /// Install from a release archive at a URL instead of the published releases. The network
+    /// and version are derived from the file name, as with --from-file.
+    #[arg(
+        long,
+        value_name = "url",
+        requires = "component",
+        conflicts_with_all = ["from_move_lock", "locked", "nightly"]
+    )]
+    from_url: Option<String>,
 
     /// Install from a branch in release mode (use --debug for debug mode).
     /// If none provided, main is used. Note that this requires Rust & cargo to be installed.
@@ -32,15 +80,46 @@ pub struct Command {
 }
 
 impl Command {
-    pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
+    pub async fn exec(&self, options: &Options) -> Result<()> {
+        let component = match self.components.as_slice() {
+            [] => None,
+            [component] => Some(component),
+            _ if self.locked || self.from_file.is_some() || self.from_url.is_some() => {
+                bail!("--locked, --from-file and --from-url install a single binary")
+            }
+            _ => None,
+        };
+
+        if self.locked {
+            return install_locked(component.map(String::as_str), self.debug, self.yes, options)
+                .await;
+        }
+
+        if self.components.is_empty() {
+            let path = self
+                .from_move_lock
+                .clone()
+                .unwrap_or_else(|| PathBuf::from("."));
+            return install_from_move_lock(&path, self.yes, options).await;
+        }
+
+        let origin = match (&self.from_file, &self.from_url) {
+            (Some(path), _) => Some(ArchiveOrigin::File(path.clone())),
+            (_, Some(url)) => Some(ArchiveOrigin::Url(url.clone())),
+            _ => None,
+        };
+        if let (Some(origin), Some(component)) = (origin, component) {
+            return install_from_archive(component, origin, self.debug, self.yes, options).await;
+        }
+
         handle_cmd(
             ComponentCommands::Add {
-                component: self.component.to_owned(),
+                components: self.components.to_owned(),
                 nightly: self.nightly.to_owned(),
                 debug: self.debug.to_owned(),
                 yes: self.yes.to_owned(),
             },
-            github_token.to_owned(),
+            options,
         )
         .await
     }
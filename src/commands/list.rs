@@ -5,6 +5,7 @@ use anyhow::Result;
 use clap::Args;
 
 use crate::handle_commands::handle_cmd;
+use crate::options::Options;
 
 use super::ComponentCommands;
 
@@ -13,7 +14,7 @@ use super::ComponentCommands;
 pub struct Command;
 
 impl Command {
-    pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
-        handle_cmd(ComponentCommands::List, github_token.to_owned()).await
+    pub async fn exec(&self, options: &Options) -> Result<()> {
+        handle_cmd(ComponentCommands::List, options).await
     }
 }
@@ -0,0 +1,24 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::lockfile::{handle_lock, LOCK_FILE};
+
+/// Record the exact release assets and SHA-256 hashes of the installed binaries in a lock file.
+/// Use `suiup install --locked` to install them elsewhere.
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Lock file to write
+    #[arg(short, long, default_value = LOCK_FILE)]
+    file: PathBuf,
+}
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        handle_lock(&self.file)
+    }
+}
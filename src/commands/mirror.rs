@@ -0,0 +1,81 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::handlers::mirror::{handle_mirror_sync, SyncOptions, VersionReq};
+use crate::options::Options;
+
+use super::BinaryName;
+
+/// Build a release mirror for hosts without access to GitHub (see `--mirror`).
+#[derive(Debug, Args)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Download release listings and assets into a mirror directory laid out as
+    /// `<repo>/releases.json` and `<repo>/<asset>`, with a `.sha256` file per asset and an
+    /// index.json. Assets already in the mirror are not downloaded again.
+    Sync {
+        /// Mirror directory
+        dir: PathBuf,
+
+        /// Binaries whose releases to mirror
+        #[arg(
+            long = "binary",
+            value_enum,
+            value_delimiter = ',',
+            default_value = "sui"
+        )]
+        binaries: Vec<BinaryName>,
+
+        /// Networks of the releases to mirror (mvr releases are not tied to a network)
+        #[arg(
+            long = "network",
+            value_delimiter = ',',
+            default_value = "testnet,devnet,mainnet"
+        )]
+        networks: Vec<String>,
+
+        /// Versions to mirror, e.g. '>=1.40.0, <1.45.0'. Defaults to all versions.
+        #[arg(long)]
+        versions: Option<VersionReq>,
+
+        /// Platforms to mirror as <os>-<arch>
+        #[arg(
+            long = "platform",
+            value_delimiter = ',',
+            default_value = "ubuntu-x86_64,ubuntu-aarch64,macos-x86_64,macos-arm64,windows-x86_64"
+        )]
+        platforms: Vec<String>,
+    },
+}
+
+impl Command {
+    pub async fn exec(&self, options: &Options) -> Result<()> {
+        match &self.command {
+            Commands::Sync {
+                dir,
+                binaries,
+                networks,
+                versions,
+                platforms,
+            } => {
+                let sync_options = SyncOptions {
+                    binaries: binaries.clone(),
+                    networks: networks.clone(),
+                    versions: versions.clone().unwrap_or_default(),
+                    platforms: platforms.clone(),
+                };
+                handle_mirror_sync(dir, &sync_options, options).await
+            }
+        }
+    }
+}
@@ -1,18 +1,34 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod apply;
 mod default;
+mod env;
 mod install;
 mod list;
+mod lock;
+mod mirror;
+mod move_;
+mod plan;
+mod profile;
 mod remove;
+mod run;
 mod self_;
 mod show;
 mod switch;
+mod toolchain;
 mod update;
+mod verify;
 mod which;
 mod cleanup;
 
-use crate::{handlers::self_::check_for_updates, types::BinaryVersion};
+use crate::{
+    config::{Config, GithubConfig},
+    handlers::self_::check_for_updates,
+    options::Options,
+    source::{http_client, repository, web_url, Credentials, Mirror},
+    types::{BinaryVersion, Repo},
+};
 
 use anyhow::{anyhow, bail, Result};
 use clap::{Parser, Subcommand, ValueEnum};
@@ -29,6 +45,42 @@ pub struct Command {
     #[arg(long, env = "GITHUB_TOKEN", global = true)]
     pub github_token: Option<String>,
 
+    /// Fail downloads of release assets that have no published SHA-256 checksum, instead of
+    /// warning and recording the computed hash.
+    #[arg(
+        long,
+        env = "SUIUP_REQUIRE_CHECKSUM",
+        global = true,
+        value_parser = clap::builder::BoolishValueParser::new()
+    )]
+    pub require_checksum: bool,
+
+    /// Fail downloads of release assets that are not signed by a key trusted for their
+    /// repository in trusted_keys.json.
+    #[arg(
+        long,
+        env = "SUIUP_REQUIRE_SIGNATURE",
+        global = true,
+        value_parser = clap::builder::BoolishValueParser::new()
+    )]
+    pub require_signature: bool,
+
+    /// Fetch release listings and assets from a mirror laid out as `<repo>/releases.json` and
+    /// `<repo>/<asset>`: an HTTP(S) URL, a `file://` URL or a directory. Defaults to the `mirror`
+    /// setting in config.toml.
+    #[arg(long, env = "SUIUP_MIRROR", global = true, value_name = "url-or-path")]
+    pub mirror: Option<String>,
+
+    /// Forbid network access: use only the cached release lists, the archives already in the
+    /// release archives folder, or a mirror directory.
+    #[arg(
+        long,
+        env = "SUIUP_OFFLINE",
+        global = true,
+        value_parser = clap::builder::BoolishValueParser::new()
+    )]
+    pub offline: bool,
+
     /// Disable update warnings for suiup itself.
     #[arg(long, env = "SUIUP_DISABLE_UPDATE_WARNINGS", global = true)]
     pub disable_update_warnings: bool,
@@ -36,39 +88,83 @@ pub struct Command {
 
 #[derive(Subcommand)]
 pub enum Commands {
+    Apply(apply::Command),
     Default(default::Command),
+    Env(env::Command),
     Install(install::Command),
     Remove(remove::Command),
     List(list::Command),
+    Lock(lock::Command),
+    Mirror(mirror::Command),
+
+    #[command(name = "move")]
+    Move(move_::Command),
+
+    Plan(plan::Command),
+    Profile(profile::Command),
+    Run(run::Command),
 
     #[command(name = "self")]
     Self_(self_::Command),
 
     Show(show::Command),
     Switch(switch::Command),
+    #[command(visible_alias = "override")]
+    Toolchain(toolchain::Command),
     Update(update::Command),
+    Verify(verify::Command),
     Which(which::Command),
     Cleanup(cleanup::Command),
 }
 
 impl Command {
     pub async fn exec(&self) -> Result<()> {
-        // Check for updates before executing any command (except self update to avoid recursion)
-        if !matches!(self.command, Commands::Self_(_)) && !self.disable_update_warnings {
-            check_for_updates();
+        let config = Config::load()?;
+        let mirror = self.mirror.clone().or(config.mirror);
+        let options = Options {
+            client: http_client(&config.http)?,
+            credentials: Credentials::load(self.github_token.clone(), &config.github),
+            github: config.github,
+            mirror: mirror.as_deref().map(Mirror::parse).transpose()?,
+            offline: self.offline,
+            require_checksum: self.require_checksum,
+            require_signature: self.require_signature,
+            progress: None,
+            best_effort: false,
+        };
+
+        // Check for updates before executing any command (except self update to avoid recursion,
+        // run and env, which must not add anything to the output meant for other programs)
+        if !matches!(
+            self.command,
+            Commands::Self_(_) | Commands::Run(_) | Commands::Env(_)
+        ) && !self.disable_update_warnings
+            && !self.offline
+        {
+            check_for_updates(options.clone());
         }
 
         match &self.command {
+            Commands::Apply(cmd) => cmd.exec(&options).await,
             Commands::Default(cmd) => cmd.exec(),
-            Commands::Install(cmd) => cmd.exec(&self.github_token).await,
-            Commands::Remove(cmd) => cmd.exec(&self.github_token).await,
-            Commands::List(cmd) => cmd.exec(&self.github_token).await,
-            Commands::Self_(cmd) => cmd.exec().await,
+            Commands::Env(cmd) => cmd.exec(),
+            Commands::Install(cmd) => cmd.exec(&options).await,
+            Commands::Remove(cmd) => cmd.exec(&options).await,
+            Commands::List(cmd) => cmd.exec(&options).await,
+            Commands::Lock(cmd) => cmd.exec(),
+            Commands::Mirror(cmd) => cmd.exec(&options).await,
+            Commands::Move(cmd) => cmd.exec(),
+            Commands::Plan(cmd) => cmd.exec(),
+            Commands::Profile(cmd) => cmd.exec(),
+            Commands::Run(cmd) => cmd.exec(&options).await,
+            Commands::Self_(cmd) => cmd.exec(&options).await,
             Commands::Show(cmd) => cmd.exec(),
             Commands::Switch(cmd) => cmd.exec(),
-            Commands::Update(cmd) => cmd.exec(&self.github_token).await,
+            Commands::Toolchain(cmd) => cmd.exec(),
+            Commands::Update(cmd) => cmd.exec(&options).await,
+            Commands::Verify(cmd) => cmd.exec(),
             Commands::Which(cmd) => cmd.exec(),
-            Commands::Cleanup(cmd) => cmd.exec(&self.github_token).await,
+            Commands::Cleanup(cmd) => cmd.exec(&options).await,
         }
     }
 }
@@ -80,10 +176,11 @@ pub enum ComponentCommands {
     #[command(about = "Add a binary")]
     Add {
         #[arg(
-            num_args = 1..=2,
-            help = "Binary to install with optional version (e.g. 'sui', 'sui@testnet-1.39.3', 'sui@testnet')"
+            num_args = 1..,
+            required = true,
+            help = "Binaries to install with optional version (e.g. 'sui', 'sui@testnet-1.39.3', 'sui@testnet')"
         )]
-        component: String,
+        components: Vec<String>,
         #[arg(
             long,
             help = "Whether to install the debug version of the binary (only available for sui). Default is false."
@@ -144,15 +241,22 @@ pub struct CommandMetadata {
 }
 
 impl BinaryName {
-    pub fn repo_url(&self) -> &str {
+    /// Returns the repository the binary is released from
+    pub fn repo(&self) -> Repo {
         match self {
-            BinaryName::Mvr => "https://github.com/MystenLabs/mvr",
-            BinaryName::Walrus => "https://github.com/MystenLabs/walrus",
-            BinaryName::WalrusSites => "https://github.com/MystenLabs/walrus-sites",
-            _ => "https://github.com/MystenLabs/sui",
+            BinaryName::Mvr => Repo::Mvr,
+            BinaryName::Sui => Repo::Sui,
+            BinaryName::Walrus => Repo::Walrus,
+            BinaryName::WalrusSites => Repo::WalrusSites,
         }
     }
 
+    /// Returns the URL of the git repository of the binary
+    pub fn repo_url(&self, github: &GithubConfig) -> String {
+        let repo = self.repo();
+        format!("{}/{}", web_url(github, &repo), repository(github, &repo))
+    }
+
     pub fn to_str(&self) -> &str {
         match self {
             BinaryName::Mvr => "mvr",
@@ -0,0 +1,45 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::handlers::move_toml::{handle_move_check, handle_move_pin};
+
+/// Check Move package framework dependencies against the active sui binary.
+#[derive(Debug, Args)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Check that the Sui/MoveStdlib `rev` in Move.toml matches the active sui binary
+    Check {
+        /// Package directory or path to Move.toml
+        #[arg(long, default_value = ".")]
+        path: PathBuf,
+    },
+    /// Rewrite the Sui/MoveStdlib `rev` in Move.toml to match the active sui binary
+    Pin {
+        /// Package directory or path to Move.toml
+        #[arg(long, default_value = ".")]
+        path: PathBuf,
+
+        /// Pin to the exact release tag (e.g. 'testnet-v1.39.3') instead of 'framework/<network>'
+        #[arg(long)]
+        exact: bool,
+    },
+}
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        match &self.command {
+            Commands::Check { path } => handle_move_check(path),
+            Commands::Pin { path, exact } => handle_move_pin(path, *exact),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::manifest::handle_plan;
+
+/// Show what `apply` would install, remove and set as default for a manifest.
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Manifest describing the desired binaries
+    #[arg(short, long, default_value = "tools.toml")]
+    file: PathBuf,
+}
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        handle_plan(&self.file)
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::handlers::profile::{
+    handle_profile_create, handle_profile_deactivate, handle_profile_list, handle_profile_remove,
+    handle_profile_use,
+};
+
+/// Manage isolated sui config dirs (client.yaml, keystore, aliases) per network.
+#[derive(Debug, Args)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Create a profile with its own sui config for a network
+    Create {
+        /// Profile name, e.g. 'mainnet-ops'
+        name: String,
+
+        /// Network of the profile's client env and default binaries
+        #[arg(long, default_value = "testnet")]
+        network: String,
+
+        /// Binaries to switch to the profile's network when the profile is used
+        #[arg(long, value_delimiter = ',', default_value = "sui,walrus")]
+        binaries: Vec<String>,
+    },
+    /// List profiles
+    List,
+    /// Activate a profile and switch its binaries to the profile's network
+    Use { name: String },
+    /// Deactivate the active profile
+    Deactivate,
+    /// Remove a profile, including its keystore
+    Remove {
+        name: String,
+
+        #[arg(short, long, help = "Accept defaults without prompting")]
+        yes: bool,
+    },
+}
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        match &self.command {
+            Commands::Create {
+                name,
+                network,
+                binaries,
+            } => handle_profile_create(name, network, binaries),
+            Commands::List => handle_profile_list(),
+            Commands::Use { name } => handle_profile_use(name),
+            Commands::Deactivate => handle_profile_deactivate(),
+            Commands::Remove { name, yes } => handle_profile_remove(name, *yes),
+        }
+    }
+}
@@ -5,6 +5,7 @@ use anyhow::Result;
 use clap::Args;
 
 use crate::handle_commands::handle_cmd;
+use crate::options::Options;
 
 use super::{BinaryName, ComponentCommands};
 
@@ -16,12 +17,12 @@ pub struct Command {
 }
 
 impl Command {
-    pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
+    pub async fn exec(&self, options: &Options) -> Result<()> {
         handle_cmd(
             ComponentCommands::Remove {
                 binary: self.binary.to_owned(),
             },
-            github_token.to_owned(),
+            options,
         )
         .await
     }
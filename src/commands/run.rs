@@ -0,0 +1,38 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ffi::OsString;
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::run::handle_run;
+use crate::options::Options;
+
+/// Run a specific version of a binary without changing the default.
+#[derive(Args, Debug)]
+pub struct Command {
+    /// Binary and version to run (e.g. 'sui@testnet-1.39.3', 'walrus@mainnet', 'mvr@0.0.8').
+    /// Without a version, the binary is resolved like the default one.
+    binary_spec: String,
+
+    /// Install the binary first if it is not installed (the default version is not changed)
+    #[arg(long)]
+    install: bool,
+
+    /// Arguments passed to the binary (e.g. `suiup run sui@testnet -- client gas`)
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<OsString>,
+}
+
+impl Command {
+    pub async fn exec(&self, options: &Options) -> Result<()> {
+        handle_run(
+            &self.binary_spec,
+            self.args.to_owned(),
+            self.install,
+            options,
+        )
+        .await
+    }
+}
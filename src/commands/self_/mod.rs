@@ -7,6 +7,8 @@ mod update;
 use anyhow::Result;
 use clap::{Args, Subcommand};
 
+use crate::options::Options;
+
 /// Commands for suiup itself.
 #[derive(Debug, Args)]
 pub struct Command {
@@ -22,9 +24,9 @@ enum Commands {
 
 impl Command {
     /// Handles the self commands
-    pub async fn exec(&self) -> Result<()> {
+    pub async fn exec(&self, options: &Options) -> Result<()> {
         match &self.command {
-            Commands::Update(cmd) => cmd.exec().await,
+            Commands::Update(cmd) => cmd.exec(options).await,
             Commands::Uninstall(cmd) => cmd.exec(),
         }
     }
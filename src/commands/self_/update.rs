@@ -5,13 +5,14 @@ use anyhow::Result;
 use clap::Args;
 
 use crate::handlers::self_;
+use crate::options::Options;
 
 /// Update suiup itself.
 #[derive(Args, Debug)]
 pub struct Command;
 
 impl Command {
-    pub async fn exec(&self) -> Result<()> {
-        self_::handle_update().await
+    pub async fn exec(&self, options: &Options) -> Result<()> {
+        self_::handle_update(options).await
     }
 }
@@ -13,10 +13,15 @@ pub struct Command {
     /// e.g. 'sui@testnet', 'mvr@main', 'walrus@testnet'
     /// This will use the latest installed version for that network/release
     binary_spec: String,
+
+    /// Also make the sui client env matching the network active (edits client.yaml in
+    /// $SUI_CONFIG_DIR or ~/.sui/sui_config, keeping a backup)
+    #[arg(long)]
+    sync_env: bool,
 }
 
 impl Command {
     pub fn exec(&self) -> Result<()> {
-        handle_switch(&self.binary_spec)
+        handle_switch(&self.binary_spec, self.sync_env)
     }
 }
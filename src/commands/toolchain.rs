@@ -0,0 +1,40 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::handlers::toolchain::{
+    handle_toolchain_set, handle_toolchain_show, handle_toolchain_unset,
+};
+
+/// Manage per-project toolchain pins (suiup.toml).
+#[derive(Debug, Args)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Pin binaries for the current directory, e.g. 'sui@testnet-1.39.3', 'walrus@testnet'
+    Set {
+        #[arg(required = true, num_args = 1..)]
+        binaries: Vec<String>,
+    },
+    /// Show the toolchain file that applies to the current directory
+    Show,
+    /// Remove pins from the toolchain file in the current directory.
+    /// Removes the whole file if no binary is given.
+    Unset { binaries: Vec<String> },
+}
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        match &self.command {
+            Commands::Set { binaries } => handle_toolchain_set(binaries),
+            Commands::Show => handle_toolchain_show(),
+            Commands::Unset { binaries } => handle_toolchain_unset(binaries),
+        }
+    }
+}
@@ -5,6 +5,7 @@ use anyhow::Result;
 use clap::Args;
 
 use crate::handlers::update::handle_update;
+use crate::options::Options;
 
 /// Update binary.
 #[derive(Args, Debug)]
@@ -19,12 +20,7 @@ pub struct Command {
 }
 
 impl Command {
-    pub async fn exec(&self, github_token: &Option<String>) -> Result<()> {
-        handle_update(
-            self.name.to_owned(),
-            self.yes.to_owned(),
-            github_token.to_owned(),
-        )
-        .await
+    pub async fn exec(&self, options: &Options) -> Result<()> {
+        handle_update(self.name.to_owned(), self.yes.to_owned(), options).await
     }
 }
@@ -0,0 +1,18 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::handlers::verify::handle_verify;
+
+/// Check that installed binaries match their recorded hash and version, and that the default
+/// binaries are suiup shims.
+#[derive(Args, Debug)]
+pub struct Command;
+
+impl Command {
+    pub fn exec(&self) -> Result<()> {
+        handle_verify()
+    }
+}
@@ -4,8 +4,11 @@
 use anyhow::{anyhow, Result};
 use std::fs::create_dir_all;
 
-use crate::commands::BinaryName;
-use crate::handlers::install::{install_from_nightly, install_from_release, install_mvr};
+use crate::commands::{BinaryName, CommandMetadata};
+use crate::handlers::install::{
+    install_from_nightly, install_from_release, install_mvr, install_release_components,
+};
+use crate::options::Options;
 use crate::paths::{binaries_dir, get_default_bin_dir};
 use crate::types::{Repo, Version};
 
@@ -17,7 +20,7 @@ pub async fn install_component(
     nightly: Option<String>,
     debug: bool,
     yes: bool,
-    github_token: Option<String>,
+    options: &Options,
 ) -> Result<()> {
     // Ensure installation directories exist
     let default_bin_dir = get_default_bin_dir();
@@ -40,7 +43,7 @@ pub async fn install_component(
         (BinaryName::Walrus, nightly) => {
             create_dir_all(installed_bins_dir.join(network.clone()))?;
             if let Some(branch) = nightly {
-                install_from_nightly(&name, branch, debug, yes).await?;
+                install_from_nightly(&name, branch, debug, yes, options).await?;
             } else {
                 install_from_release(
                     name.to_string().as_str(),
@@ -49,7 +52,7 @@ pub async fn install_component(
                     debug,
                     yes,
                     Repo::Walrus,
-                    github_token,
+                    options,
                 )
                 .await?;
             }
@@ -57,7 +60,7 @@ pub async fn install_component(
         (BinaryName::WalrusSites, nightly) => {
             create_dir_all(installed_bins_dir.join("mainnet"))?;
             if let Some(branch) = nightly {
-                install_from_nightly(&name, branch, debug, yes).await?;
+                install_from_nightly(&name, branch, debug, yes, options).await?;
             } else {
                 install_from_release(
                     name.to_string().as_str(),
@@ -66,7 +69,7 @@ pub async fn install_component(
                     debug,
                     yes,
                     Repo::WalrusSites,
-                    github_token,
+                    options,
                 )
                 .await?;
             }
@@ -75,13 +78,13 @@ pub async fn install_component(
         (BinaryName::Mvr, nightly) => {
             create_dir_all(installed_bins_dir.join("standalone"))?;
             if let Some(branch) = nightly {
-                install_from_nightly(&name, branch, debug, yes).await?;
+                install_from_nightly(&name, branch, debug, yes, options).await?;
             } else {
-                install_mvr(version, yes).await?;
+                install_mvr(version, yes, options).await?;
             }
         }
         (_, Some(branch)) => {
-            install_from_nightly(&name, branch, debug, yes).await?;
+            install_from_nightly(&name, branch, debug, yes, options).await?;
         }
         _ => {
             install_from_release(
@@ -91,7 +94,7 @@ pub async fn install_component(
                 debug,
                 yes,
                 Repo::Sui,
-                github_token,
+                options,
             )
             .await?;
         }
@@ -99,3 +102,14 @@ pub async fn install_component(
 
     Ok(())
 }
+
+/// Install several components at once, downloading them concurrently
+pub async fn install_components(
+    components: &[CommandMetadata],
+    debug: bool,
+    yes: bool,
+    options: &Options,
+) -> Result<()> {
+    create_dir_all(get_default_bin_dir())?;
+    install_release_components(components, debug, yes, options).await
+}
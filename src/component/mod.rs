@@ -5,21 +5,22 @@ mod install;
 mod list;
 mod remove;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 
 use crate::commands::{
     parse_component_with_version, BinaryName, CommandMetadata, ComponentCommands,
 };
+use crate::options::Options;
 
 /// ComponentManager handles all component-related operations
 pub struct ComponentManager {
-    github_token: Option<String>,
+    options: Options,
 }
 
 impl ComponentManager {
     /// Create a new ComponentManager instance
-    pub fn new(github_token: Option<String>) -> Self {
-        Self { github_token }
+    pub fn new(options: Options) -> Self {
+        Self { options }
     }
 
     /// Handle component commands
@@ -27,14 +28,24 @@ impl ComponentManager {
         match cmd {
             ComponentCommands::List => self.list_components().await,
             ComponentCommands::Add {
-                component,
+                components,
                 nightly,
                 debug,
                 yes,
             } => {
-                let command_metadata = parse_component_with_version(&component)?;
-                self.install_component(command_metadata, nightly, debug, yes)
-                    .await
+                let mut components = components
+                    .iter()
+                    .map(|c| parse_component_with_version(c))
+                    .collect::<Result<Vec<_>>>()?;
+                if components.len() == 1 {
+                    let command_metadata = components.remove(0);
+                    self.install_component(command_metadata, nightly, debug, yes)
+                        .await
+                } else if nightly.is_some() {
+                    bail!("Only one binary can be installed from a branch at a time")
+                } else {
+                    self.install_components(&components, debug, yes).await
+                }
             }
             ComponentCommands::Remove { binary } => self.remove_component(binary).await,
             ComponentCommands::Cleanup { all, days, dry_run } => self.handle_cleanup(all, days, dry_run).await
@@ -66,11 +77,21 @@ impl ComponentManager {
             nightly,
             debug,
             yes,
-            self.github_token.clone(),
+            &self.options,
         )
         .await
     }
 
+    /// Install several components, downloading them concurrently
+    async fn install_components(
+        &self,
+        components: &[CommandMetadata],
+        debug: bool,
+        yes: bool,
+    ) -> Result<()> {
+        install::install_components(components, debug, yes, &self.options).await
+    }
+
     /// Remove a component
     async fn remove_component(&self, binary: BinaryName) -> Result<()> {
         remove::remove_component(binary).await
@@ -0,0 +1,202 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! User settings, read from `config.toml` in the suiup config dir. Command line flags and
+//! environment variables take precedence over these settings.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Error};
+use serde::Deserialize;
+
+use crate::paths::get_config_file;
+
+pub const CONFIG_FILE: &str = "config.toml";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    /// Mirror to fetch release listings and assets from instead of GitHub: an HTTP(S) URL, a
+    /// `file://` URL or a directory
+    pub mirror: Option<String>,
+    /// Settings of the HTTP client, in the `[http]` table
+    #[serde(default)]
+    pub http: HttpConfig,
+    /// GitHub instance releases are fetched from, in the `[github]` table
+    #[serde(default)]
+    pub github: GithubConfig,
+}
+
+/// GitHub instance releases are fetched from, e.g. a GitHub Enterprise server. The URLs apply to
+/// all binaries, unless a binary has its own in a `[github.<binary>]` table, e.g. `[github.sui]`.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct GithubConfig {
+    /// Base URL of the REST API, e.g. `https://ghe.example.com/api/v3`
+    pub api_url: Option<String>,
+    /// Base URL of the web interface, e.g. `https://ghe.example.com`
+    pub web_url: Option<String>,
+    /// Settings of single binaries, by binary name
+    #[serde(flatten)]
+    pub binaries: HashMap<String, GithubBinaryConfig>,
+}
+
+/// GitHub settings of a single binary
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct GithubBinaryConfig {
+    pub api_url: Option<String>,
+    pub web_url: Option<String>,
+    /// Repository to install the binary from instead of the official one, e.g. a fork:
+    /// `our-org/sui`
+    pub repository: Option<String>,
+    /// Whether the repository is private. Assets of private repositories are downloaded through
+    /// the GitHub API with the token, their download URLs only work in a browser session.
+    #[serde(default)]
+    pub private: bool,
+}
+
+impl GithubConfig {
+    /// Returns the API base URL configured for a binary, if any
+    pub fn api_url(&self, binary: &str) -> Option<&str> {
+        self.binaries
+            .get(binary)
+            .and_then(|b| b.api_url.as_deref())
+            .or(self.api_url.as_deref())
+    }
+
+    /// Returns the web base URL configured for a binary, if any
+    pub fn web_url(&self, binary: &str) -> Option<&str> {
+        self.binaries
+            .get(binary)
+            .and_then(|b| b.web_url.as_deref())
+            .or(self.web_url.as_deref())
+    }
+
+    /// Returns the repository configured for a binary, if any
+    pub fn repository(&self, binary: &str) -> Option<&str> {
+        self.binaries
+            .get(binary)
+            .and_then(|b| b.repository.as_deref())
+    }
+
+    /// Whether the repository of a binary is private
+    pub fn is_private(&self, binary: &str) -> bool {
+        self.binaries.get(binary).is_some_and(|b| b.private)
+    }
+}
+
+/// HTTP client settings. The standard proxy environment variables (`HTTPS_PROXY`, `HTTP_PROXY`,
+/// `ALL_PROXY`, `NO_PROXY`) and `SUIUP_CA_BUNDLE` take precedence.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct HttpConfig {
+    /// Proxy URL for all requests
+    pub proxy: Option<String>,
+    /// Comma separated hosts and domains to reach without the proxy
+    pub no_proxy: Option<String>,
+    /// PEM file with additional CA certificates to trust, e.g. the certificate of a proxy that
+    /// intercepts TLS
+    pub ca_bundle: Option<PathBuf>,
+    /// Timeout to connect to a server, in seconds
+    pub connect_timeout: Option<u64>,
+    /// Timeout for each read from a server, in seconds
+    pub read_timeout: Option<u64>,
+}
+
+impl Config {
+    /// Loads the settings from `config.toml`, or the defaults if the file does not exist
+    pub fn load() -> Result<Self, Error> {
+        let path = get_config_file(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+        Self::parse(&content).map_err(|e| anyhow!("Cannot parse {}: {e}", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(content)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse("mirror = \"https://mirror.example.com/suiup\"\n").unwrap();
+        assert_eq!(
+            config.mirror.as_deref(),
+            Some("https://mirror.example.com/suiup")
+        );
+        assert!(Config::parse("").unwrap().mirror.is_none());
+        assert!(Config::parse("mirror = 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_http_config() {
+        let config = Config::parse(
+            r#"
+[http]
+proxy = "http://proxy.example.com:3128"
+no_proxy = "localhost,.internal.example.com"
+ca_bundle = "/etc/ssl/certs/corporate.pem"
+connect_timeout = 10
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.http.proxy.as_deref(),
+            Some("http://proxy.example.com:3128")
+        );
+        assert_eq!(
+            config.http.ca_bundle,
+            Some(PathBuf::from("/etc/ssl/certs/corporate.pem"))
+        );
+        assert_eq!(config.http.connect_timeout, Some(10));
+        assert_eq!(config.http.read_timeout, None);
+        assert!(Config::parse("").unwrap().http.proxy.is_none());
+    }
+
+    #[test]
+    fn test_parse_github_config() {
+        let config = Config::parse(
+            r#"
+[github]
+api_url = "https://ghe.example.com/api/v3"
+
+[github.sui]
+api_url = "https://ghe-sui.example.com/api/v3"
+web_url = "https://ghe-sui.example.com"
+"#,
+        )
+        .unwrap();
+        let github = config.github;
+        assert_eq!(
+            github.api_url("sui"),
+            Some("https://ghe-sui.example.com/api/v3")
+        );
+        assert_eq!(github.web_url("sui"), Some("https://ghe-sui.example.com"));
+        assert_eq!(
+            github.api_url("walrus"),
+            Some("https://ghe.example.com/api/v3")
+        );
+        assert_eq!(github.web_url("walrus"), None);
+        assert!(Config::parse("").unwrap().github.api_url("sui").is_none());
+        assert!(Config::parse("[github]\nsui = 1").is_err());
+
+        let config = Config::parse(
+            r#"
+[github.sui]
+repository = "our-org/sui"
+private = true
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.github.repository("sui"), Some("our-org/sui"));
+        assert!(config.github.is_private("sui"));
+        assert_eq!(config.github.repository("walrus"), None);
+        assert!(!config.github.is_private("walrus"));
+        assert_eq!(config.github.api_url("sui"), None);
+    }
+}
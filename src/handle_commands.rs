@@ -5,10 +5,11 @@ use anyhow::Error;
 
 use crate::commands::ComponentCommands;
 use crate::component::ComponentManager;
+use crate::options::Options;
 
 /// Handle component commands by delegating to the ComponentManager
-pub async fn handle_cmd(cmd: ComponentCommands, github_token: Option<String>) -> Result<(), Error> {
-    let manager = ComponentManager::new(github_token);
+pub async fn handle_cmd(cmd: ComponentCommands, options: &Options) -> Result<(), Error> {
+    let manager = ComponentManager::new(options.clone());
     manager.handle_command(cmd).await
 }
 
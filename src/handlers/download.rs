@@ -1,26 +1,53 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::handlers::checksum::sha256_file;
 use crate::handlers::release::{
     ensure_version_prefix, find_last_release_by_network, find_networks_with_version,
 };
+use crate::handlers::signature::verify_asset_signature;
 use crate::handlers::version::extract_version_from_release;
-use crate::types::Repo;
-use crate::{handlers::release::release_list, paths::release_archive_dir, types::Release};
+use crate::options::Options;
+use crate::source::{
+    backoff, open_url, release_source, AssetDownload, ReleaseSource, Resume, MAX_ATTEMPTS,
+};
+use crate::types::{Asset, Provenance, Repo};
+use crate::{paths::release_archive_dir, types::Release};
 use anyhow::{anyhow, bail, Error};
 use futures_util::StreamExt;
 use indicatif::{HumanBytes, ProgressBar, ProgressStyle};
-use md5::Context;
-use reqwest::{
-    header::{HeaderMap, HeaderValue, USER_AGENT},
-    Client,
-};
-use std::fs::File;
-use std::io::Read;
+use sha2::{Digest, Sha256};
+use std::fmt::{self, Display, Formatter};
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::path::Path;
 use std::{cmp::min, io::Write, path::PathBuf, time::Instant};
 
 use tracing::debug;
 
+/// A release asset downloaded to the release archives folder
+#[derive(Debug, Clone)]
+pub struct DownloadedAsset {
+    /// File name of the asset in the release archives folder
+    pub name: String,
+    /// Tag of the release the asset belongs to
+    pub tag: String,
+    pub url: String,
+    /// SHA-256 of the downloaded file
+    pub sha256: String,
+}
+
+impl DownloadedAsset {
+    pub fn provenance(&self) -> Provenance {
+        Provenance {
+            tag: self.tag.clone(),
+            asset: self.name.clone(),
+            url: self.url.clone(),
+            asset_sha256: self.sha256.clone(),
+        }
+    }
+}
+
 /// Generate helpful error message with network suggestions
 /// Note: This is only applicable for sui and walrus. MVR binary is standalone, not tied to a network.
 fn generate_network_suggestions_error(
@@ -120,51 +147,39 @@ pub async fn download_release_at_version(
     repo: Repo,
     network: &str,
     version: &str,
-    github_token: Option<String>,
-) -> Result<String, anyhow::Error> {
+    options: &Options,
+) -> Result<DownloadedAsset, anyhow::Error> {
     let (os, arch) = detect_os_arch()?;
+    let source = release_source(options);
+    let releases = source.list_releases(&repo).await?;
+    let release =
+        find_release_at_version(source.as_ref(), &repo, &releases, network, version).await?;
+    download_release_asset(source.as_ref(), &repo, &release, &os, &arch, options).await
+}
 
+/// Finds the release of a network with a specific version in the release list of a repository.
+/// Releases missing from the list are looked up by tag.
+pub async fn find_release_at_version(
+    source: &dyn ReleaseSource,
+    repo: &Repo,
+    releases: &[Release],
+    network: &str,
+    version: &str,
+) -> Result<Release, Error> {
     // Ensure version has 'v' prefix for GitHub release tags
     let version = ensure_version_prefix(version);
 
     let tag = format!("{}-{}", network, version);
 
     println!("Searching for release with tag: {}...", tag);
-    let client = reqwest::Client::new();
-    let mut headers = HeaderMap::new();
-
-    let releases = release_list(&repo, github_token.clone()).await?.0;
-
-    if let Some(release) = releases
+    match releases
         .iter()
         .find(|r| r.assets.iter().any(|a| a.name.contains(&tag)))
     {
-        download_asset_from_github(release, &os, &arch, github_token).await
-    } else {
-        headers.insert(USER_AGENT, HeaderValue::from_static("suiup"));
-
-        // Add authorization header if token is provided
-        if let Some(token) = &github_token {
-            headers.insert(
-                "Authorization",
-                HeaderValue::from_str(&format!("token {}", token)).unwrap(),
-            );
-        }
-
-        let url = format!("https://api.github.com/repos/{repo}/releases/tags/{}", tag);
-        let response = client.get(&url).headers(headers).send().await?;
-
-        if !response.status().is_success() {
-            return Err(generate_network_suggestions_error(
-                &repo,
-                &releases,
-                Some(&version),
-                network,
-            ));
-        }
-
-        let release: Release = response.json().await?;
-        download_asset_from_github(&release, &os, &arch, github_token).await
+        Some(release) => Ok(release.clone()),
+        None => source.release_by_tag(repo, &tag).await?.ok_or_else(|| {
+            generate_network_suggestions_error(repo, releases, Some(&version), network)
+        }),
     }
 }
 
@@ -172,96 +187,180 @@ pub async fn download_release_at_version(
 pub async fn download_latest_release(
     repo: Repo,
     network: &str,
-    github_token: Option<String>,
-) -> Result<String, anyhow::Error> {
+    options: &Options,
+) -> Result<DownloadedAsset, anyhow::Error> {
     println!("Downloading release list");
     debug!("Downloading release list for repo: {repo} and network: {network}");
-    let releases = release_list(&repo, github_token.clone()).await?;
+    let source = release_source(options);
+    let releases = source.list_releases(&repo).await?;
 
     let (os, arch) = detect_os_arch()?;
 
-    let last_release = find_last_release_by_network(releases.0.clone(), network)
+    let last_release = find_latest_release(&repo, &releases, network).await?;
+
+    download_release_asset(source.as_ref(), &repo, &last_release, &os, &arch, options).await
+}
+
+/// Finds the latest release of a network in the release list of a repository
+pub async fn find_latest_release(
+    repo: &Repo,
+    releases: &[Release],
+    network: &str,
+) -> Result<Release, Error> {
+    let last_release = find_last_release_by_network(releases.to_vec(), network)
         .await
-        .ok_or_else(|| generate_network_suggestions_error(&repo, &releases.0, None, network))?;
+        .ok_or_else(|| generate_network_suggestions_error(repo, releases, None, network))?;
 
     println!(
         "Last {network} release: {}",
         extract_version_from_release(&last_release.assets[0].name)?
     );
-
-    download_asset_from_github(&last_release, &os, &arch, github_token).await
+    Ok(last_release)
 }
 
+/// Downloads `url` to `download_to`, hashing it with SHA-256 while streaming, and returns the hex
+/// encoded digest. If `expected_sha256` is given, a mismatching download is deleted and an error
+/// is returned. A file of the same size already in the cache is reused if its digest matches.
+///
+/// The download is written to a `.part` file next to `download_to` first. An interrupted download
+/// is continued from there with a range request, if the server supports it.
 pub async fn download_file(
     url: &str,
     download_to: &PathBuf,
     name: &str,
-    github_token: Option<String>,
+    expected_sha256: Option<&str>,
+    options: &Options,
 ) -> Result<String, Error> {
-    let client = Client::new();
+    if options.offline {
+        bail!("Cannot download {url} in offline mode");
+    }
+    download_with_retries(
+        |resume| open_url(options, url, resume),
+        download_to,
+        name,
+        expected_sha256,
+        options,
+    )
+    .await
+}
 
-    // Start with a basic request
-    let mut request = client.get(url).header("User-Agent", "suiup");
+/// Downloads a release asset from a release source to `download_to`, like [`download_file`]
+pub async fn download_asset(
+    source: &dyn ReleaseSource,
+    repo: &Repo,
+    asset: &Asset,
+    download_to: &PathBuf,
+    expected_sha256: Option<&str>,
+    options: &Options,
+) -> Result<String, Error> {
+    download_with_retries(
+        |resume| match resume {
+            Some(resume) => source.resume_asset(repo, asset, resume),
+            None => source.open_asset(repo, asset),
+        },
+        download_to,
+        &asset.name,
+        expected_sha256,
+        options,
+    )
+    .await
+}
 
-    // Add authorization header if token is provided and the URL is from GitHub
-    if let Some(token) = github_token {
-        if url.contains("github.com") {
-            request = request.header("Authorization", format!("token {}", token));
-        }
+/// A download that broke off before the whole asset was received, e.g. on a connection reset
+#[derive(Debug)]
+struct InterruptedDownload(String);
+
+impl Display for InterruptedDownload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "download interrupted: {}", self.0)
     }
+}
 
-    let response = request.send().await?;
+impl std::error::Error for InterruptedDownload {}
 
-    let response = response.error_for_status();
+/// Returns the file a download to `download_to` is written to until it is complete
+fn part_file(download_to: &Path) -> PathBuf {
+    let mut part = download_to.as_os_str().to_owned();
+    part.push(".part");
+    PathBuf::from(part)
+}
 
-    if let Err(ref e) = response {
-        bail!("Encountered unexpected error: {e}");
-    }
+/// Returns the file the validator of a partial download is kept in, see [`Resume`]
+fn validator_file(download_to: &Path) -> PathBuf {
+    let mut validator = part_file(download_to).into_os_string();
+    validator.push(".validator");
+    PathBuf::from(validator)
+}
 
-    let response = response.unwrap();
+/// Returns the partial download to continue, if a previous download to `download_to` broke off
+/// and the server supports range requests
+fn partial_download(download_to: &Path) -> Option<Resume> {
+    let offset = part_file(download_to).metadata().ok()?.len();
+    let validator = std::fs::read_to_string(validator_file(download_to)).ok()?;
+    (offset > 0 && !validator.is_empty()).then_some(Resume { offset, validator })
+}
 
-    if !response.status().is_success() {
-        return Err(anyhow!("Failed to download: {}", response.status()));
+/// Opens a download with `open` and saves it, opening it again with exponential backoff when the
+/// download is interrupted. `open` is given the partial download to continue, if any.
+async fn download_with_retries<F, Fut>(
+    open: F,
+    download_to: &PathBuf,
+    name: &str,
+    expected_sha256: Option<&str>,
+    options: &Options,
+) -> Result<String, Error>
+where
+    F: Fn(Option<Resume>) -> Fut,
+    Fut: Future<Output = Result<AssetDownload, Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        let download = open(partial_download(download_to)).await?;
+        match save_download(download, download_to, name, expected_sha256, options).await {
+            Err(e) if e.is::<InterruptedDownload>() && attempt < MAX_ATTEMPTS => {
+                let wait = backoff(attempt);
+                options.println(format!(
+                    "Download of {name} failed ({e}), retrying in {:.1}s ({attempt}/{})...",
+                    wait.as_secs_f32(),
+                    MAX_ATTEMPTS - 1
+                ));
+                tokio::time::sleep(wait).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
     }
+}
 
-    let mut total_size = response.content_length().unwrap_or(0);
-    //walrus is on google storage, so different content length header
-    if total_size == 0 {
-        total_size = response
-            .headers()
-            .get("x-goog-stored-content-length")
-            .and_then(|c| c.to_str().ok())
-            .and_then(|c| c.parse::<u64>().ok())
-            .unwrap_or(0);
-    }
+/// Writes an asset download to `download_to`, hashing it with SHA-256 while streaming, and returns
+/// the hex encoded digest. A resumed download is appended to the `.part` file. See
+/// [`download_file`].
+async fn save_download(
+    download: AssetDownload,
+    download_to: &PathBuf,
+    name: &str,
+    expected_sha256: Option<&str>,
+    options: &Options,
+) -> Result<String, Error> {
+    let total_size = download.size.unwrap_or(0);
 
     if download_to.exists() {
         if download_to.metadata()?.len() == total_size {
-            // Check md5 if .md5 file exists
-            let md5_path = download_to.with_extension("md5");
-            if md5_path.exists() {
-                let mut file = File::open(download_to)?;
-                let mut hasher = Context::new();
-                let mut buffer = [0u8; 8192];
-                loop {
-                    let n = file.read(&mut buffer)?;
-                    if n == 0 {
-                        break;
-                    }
-                    hasher.consume(&buffer[..n]);
+            let digest = sha256_file(download_to)?;
+            match expected_sha256 {
+                Some(expected) if !expected.eq_ignore_ascii_case(&digest) => {
+                    options.println(format!(
+                        "SHA-256 mismatch for cached {name}, re-downloading..."
+                    ));
+                }
+                Some(_) => {
+                    options.println(format!("Found {name} in cache, SHA-256 verified"));
+                    return Ok(digest);
                 }
-                let result = hasher.finalize();
-                let local_md5 = format!("{:x}", result);
-                let expected_md5 = std::fs::read_to_string(md5_path)?.trim().to_string();
-                if local_md5 == expected_md5 {
-                    println!("Found {name} in cache, md5 verified");
-                    return Ok(name.to_string());
-                } else {
-                    println!("MD5 mismatch for {name}, re-downloading...");
+                None => {
+                    options.println(format!("Found {name} in cache"));
+                    return Ok(digest);
                 }
-            } else {
-                println!("Found {name} in cache (no md5 to check)");
-                return Ok(name.to_string());
             }
         }
         std::fs::remove_file(download_to)?;
@@ -269,101 +368,282 @@ pub async fn download_file(
 
     let pb = ProgressBar::new(total_size);
     pb.set_style(ProgressStyle::default_bar()
-        .template("Downloading release: {spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta}) {msg}")
+        .template("Downloading {prefix}: {spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta}) {msg}")
         .unwrap()
         .progress_chars("=>-"));
+    // concurrent downloads each get a line, named after the asset
+    let pb = match &options.progress {
+        Some(multi) => {
+            pb.set_prefix(name.to_string());
+            multi.add(pb)
+        }
+        None => {
+            pb.set_prefix("release");
+            pb
+        }
+    };
 
-    let mut file = std::fs::File::create(download_to)?;
-    let mut downloaded: u64 = 0;
-    let mut stream = response.bytes_stream();
+    let part = part_file(download_to);
+    let mut hasher = Sha256::new();
+    let mut file = if download.offset > 0 {
+        options.println(format!(
+            "Resuming download of {name} from {}",
+            HumanBytes(download.offset)
+        ));
+        let mut file = OpenOptions::new().read(true).append(true).open(&part)?;
+        std::io::copy(&mut file, &mut hasher)?;
+        file
+    } else {
+        std::fs::File::create(&part)?
+    };
+    match &download.validator {
+        Some(validator) => std::fs::write(validator_file(download_to), validator)?,
+        None => remove_if_exists(&validator_file(download_to))?,
+    }
+
+    let offset = download.offset;
+    let mut downloaded = offset;
+    pb.set_position(min(downloaded, total_size));
+    let mut stream = download.stream;
     let start = Instant::now();
 
     while let Some(item) = stream.next().await {
-        let chunk = item?;
+        let chunk = item.map_err(|e| InterruptedDownload(e.to_string()))?;
         file.write_all(&chunk)?;
-        let new = min(downloaded + (chunk.len() as u64), total_size);
-        downloaded = new;
-        pb.set_position(new);
+        hasher.update(&chunk);
+        downloaded += chunk.len() as u64;
+        pb.set_position(min(downloaded, total_size));
 
         let elapsed = start.elapsed().as_secs_f64();
         if elapsed > 0.0 {
-            let speed = downloaded as f64 / elapsed;
+            let speed = (downloaded - offset) as f64 / elapsed;
             pb.set_message(format!("Speed: {}/s", HumanBytes(speed as u64)));
         }
     }
+    drop(file);
+
+    if downloaded < total_size {
+        return Err(
+            InterruptedDownload(format!("received {downloaded} of {total_size} bytes")).into(),
+        );
+    }
 
     pb.finish_with_message("Download complete");
 
-    // After download, check md5 if .md5 file exists
-    let md5_path = download_to.with_extension("md5");
-    if md5_path.exists() {
-        let mut file = File::open(download_to)?;
-        let mut hasher = Context::new();
-        let mut buffer = [0u8; 8192];
-        loop {
-            let n = file.read(&mut buffer)?;
-            if n == 0 {
-                break;
+    let digest = format!("{:x}", hasher.finalize());
+    remove_if_exists(&validator_file(download_to))?;
+    if let Some(expected) = expected_sha256 {
+        if !expected.eq_ignore_ascii_case(&digest) {
+            std::fs::remove_file(&part)?;
+            bail!("SHA-256 check failed for {name}: expected {expected}, got {digest}");
+        }
+        options.println(format!("SHA-256 check passed for {name}"));
+    }
+    std::fs::rename(&part, download_to)?;
+
+    Ok(digest)
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), Error> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Names of the checksum assets that can be published next to a release asset, most specific
+/// first
+fn checksum_asset_names(asset: &str) -> Vec<String> {
+    vec![
+        format!("{asset}.sha256"),
+        format!("{asset}.sha256sum"),
+        "SHA256SUMS".to_string(),
+        "SHA256SUMS.txt".to_string(),
+        "sha256sums.txt".to_string(),
+        "checksums.txt".to_string(),
+    ]
+}
+
+/// Returns the SHA-256 for `asset` from a checksum file, either a bare digest or `sha256sum`
+/// output (`<digest>  <file name>`)
+pub fn parse_checksum(content: &str, asset: &str) -> Option<String> {
+    let is_digest = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+
+    content.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let digest = parts.next().filter(|d| is_digest(d))?;
+        match parts.next() {
+            // binary mode entries are prefixed with `*`, paths may include folders
+            Some(file) => {
+                let file = file.trim_start_matches('*');
+                (file == asset || file.ends_with(&format!("/{asset}")))
+                    .then(|| digest.to_lowercase())
             }
-            hasher.consume(&buffer[..n]);
+            None => Some(digest.to_lowercase()),
         }
-        let result = hasher.finalize();
-        let local_md5 = format!("{:x}", result);
-        let expected_md5 = std::fs::read_to_string(md5_path)?.trim().to_string();
-        if local_md5 != expected_md5 {
-            return Err(anyhow!(format!(
-                "MD5 check failed for {}: expected {}, got {}",
-                name, expected_md5, local_md5
-            )));
-        } else {
-            println!("MD5 check passed for {name}");
+    })
+}
+
+/// Downloads a small text asset published next to `asset`, such as a checksum or signature file.
+/// Files specific to the asset are kept in the release archives folder for offline installs.
+pub async fn fetch_sidecar(
+    source: &dyn ReleaseSource,
+    repo: &Repo,
+    sidecar: &Asset,
+    asset: &str,
+    options: &Options,
+) -> Result<String, Error> {
+    let content = source.open_asset(repo, sidecar).await?.text().await?;
+    if !options.offline && sidecar.name.starts_with(asset) {
+        std::fs::write(release_archive_dir().join(&sidecar.name), &content)?;
+    }
+    Ok(content)
+}
+
+/// Fetches the SHA-256 published for `asset` among the assets of its release, if the release
+/// publishes a checksum file for it
+pub async fn published_checksum(
+    source: &dyn ReleaseSource,
+    repo: &Repo,
+    assets: &[Asset],
+    asset: &str,
+    options: &Options,
+) -> Result<Option<String>, Error> {
+    for checksum_name in checksum_asset_names(asset) {
+        let Some(checksum_asset) = assets.iter().find(|a| a.name == checksum_name) else {
+            continue;
+        };
+
+        let content = match fetch_sidecar(source, repo, checksum_asset, asset, options).await {
+            Ok(content) => content,
+            // release wide checksum files are not kept for offline installs
+            Err(_) if options.offline && !checksum_name.starts_with(asset) => continue,
+            Err(e) => bail!("Cannot download checksum file {checksum_name}: {e}"),
+        };
+
+        if let Some(digest) = parse_checksum(&content, asset) {
+            return Ok(Some(digest));
         }
+        debug!("{checksum_name} has no checksum for {asset}");
+    }
+    Ok(None)
+}
+
+/// Downloads a release asset, verifying it against the checksum published with the release.
+/// Without a published checksum, the download fails with `--require-checksum`, otherwise a
+/// warning is printed. The signature of the asset is then checked against the keys trusted for
+/// the repository. Returns the SHA-256 of the asset.
+pub async fn download_verified_asset(
+    source: &dyn ReleaseSource,
+    repo: &Repo,
+    assets: &[Asset],
+    asset: &Asset,
+    download_to: &PathBuf,
+    options: &Options,
+) -> Result<String, Error> {
+    let expected = published_checksum(source, repo, assets, &asset.name, options).await?;
+    if expected.is_none() && options.require_checksum {
+        bail!(
+            "No checksum published for {} and --require-checksum is set",
+            asset.name
+        );
+    }
+
+    let digest = download_asset(
+        source,
+        repo,
+        asset,
+        download_to,
+        expected.as_deref(),
+        options,
+    )
+    .await?;
+
+    if expected.is_none() {
+        options.println(format!(
+            "WARNING: no checksum published for {}, recording the computed SHA-256 {digest}",
+            asset.name
+        ));
     }
 
-    Ok(name.to_string())
+    verify_asset_signature(source, repo, assets, asset, download_to, options).await?;
+    Ok(digest)
 }
 
-/// Downloads the archived release from GitHub and returns the file name
+/// Downloads the archived release from the release source and returns the downloaded asset
 /// The `network, os, and arch` parameters are used to retrieve the correct release for the target
 /// architecture and OS
-async fn download_asset_from_github(
+pub async fn download_release_asset(
+    source: &dyn ReleaseSource,
+    repo: &Repo,
     release: &Release,
     os: &str,
     arch: &str,
-    github_token: Option<String>,
-) -> Result<String, anyhow::Error> {
+    options: &Options,
+) -> Result<DownloadedAsset, anyhow::Error> {
     let asset = release
         .assets
         .iter()
         .find(|&a| a.name.contains(arch) && a.name.contains(os.to_string().to_lowercase().as_str()))
         .ok_or_else(|| anyhow!("Asset not found for {os}-{arch}"))?;
 
-    let url = asset.clone().browser_download_url;
-    let name = asset.clone().name;
     let path = release_archive_dir();
     let mut file_path = path.clone();
     file_path.push(&asset.name);
 
-    download_file(&url, &file_path, &name, github_token).await
+    let sha256 =
+        download_verified_asset(source, repo, &release.assets, asset, &file_path, options).await?;
+
+    Ok(DownloadedAsset {
+        name: asset.name.clone(),
+        tag: release.tag_name.clone(),
+        url: source.asset_url(repo, asset),
+        sha256,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::types::{Asset, Release};
+    use bytes::Bytes;
+    use futures_util::stream::{self, BoxStream};
 
     fn create_test_release(asset_names: Vec<&str>) -> Release {
         Release {
+            tag_name: String::new(),
             assets: asset_names
                 .into_iter()
                 .map(|name| Asset {
                     name: name.to_string(),
                     browser_download_url: format!("https://example.com/{}", name),
+                    ..Default::default()
                 })
                 .collect(),
         }
     }
 
+    #[test]
+    fn test_parse_checksum() {
+        let digest = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+        let asset = "sui-testnet-v1.40.1-ubuntu-x86_64.tgz";
+
+        // bare digest sidecar
+        assert_eq!(
+            parse_checksum(&format!("{digest}\n"), asset),
+            Some(digest.to_string())
+        );
+        // sha256sum output with several files, binary mode and folders
+        let sums = format!(
+            "{}  sui-testnet-v1.40.1-macos-arm64.tgz\n{} *release/{asset}\n",
+            "0".repeat(64),
+            digest.to_uppercase()
+        );
+        assert_eq!(parse_checksum(&sums, asset), Some(digest.to_string()));
+        assert_eq!(parse_checksum(&sums, "walrus.tgz"), None);
+        assert_eq!(parse_checksum("not a checksum", asset), None);
+    }
+
     #[test]
     fn test_binary_name() {
         assert_eq!(Repo::Sui.binary_name(), "sui");
@@ -426,4 +706,60 @@ mod tests {
         assert!(error_msg.contains("MVR is a standalone binary"));
         assert!(error_msg.contains("suiup install mvr"));
     }
+
+    fn stream_of(
+        chunks: Vec<Result<&'static [u8], Error>>,
+    ) -> BoxStream<'static, Result<Bytes, Error>> {
+        stream::iter(chunks.into_iter().map(|c| c.map(Bytes::from_static))).boxed()
+    }
+
+    #[tokio::test]
+    async fn test_resume_partial_download() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let download_to = temp_dir.path().join("sui.tgz");
+        let download = |offset, stream| AssetDownload {
+            url: "https://example.com/sui.tgz".to_string(),
+            size: Some(11),
+            offset,
+            validator: Some("\"v1\"".to_string()),
+            stream,
+        };
+
+        // the connection breaks after the first chunk
+        let interrupted = stream_of(vec![Ok(b"hello"), Err(anyhow!("connection reset"))]);
+        let error = save_download(
+            download(0, interrupted),
+            &download_to,
+            "sui.tgz",
+            None,
+            &Options::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(error.is::<InterruptedDownload>());
+        assert!(!download_to.exists());
+        assert_eq!(
+            partial_download(&download_to),
+            Some(Resume {
+                offset: 5,
+                validator: "\"v1\"".to_string()
+            })
+        );
+
+        // the rest is appended to the partial download and hashed with it
+        let rest = stream_of(vec![Ok(b" world")]);
+        let digest = save_download(
+            download(5, rest),
+            &download_to,
+            "sui.tgz",
+            None,
+            &Options::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(std::fs::read(&download_to).unwrap(), b"hello world");
+        assert_eq!(digest, sha256_file(&download_to).unwrap());
+        assert!(!part_file(&download_to).exists());
+        assert_eq!(partial_download(&download_to), None);
+    }
 }
@@ -1,21 +1,32 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
 
 use super::check_if_binaries_exist;
 use super::version::extract_version_from_release;
-use crate::commands::BinaryName;
-use crate::handlers::download::{download_latest_release, download_release_at_version};
-use crate::handlers::{extract_component, update_after_install};
+use crate::commands::{BinaryName, CommandMetadata};
+use crate::handlers::checksum::sha256_file;
+use crate::handlers::download::{
+    detect_os_arch, download_file, download_latest_release, download_release_asset,
+    download_release_at_version, find_latest_release, find_release_at_version, DownloadedAsset,
+};
+use crate::handlers::release::ensure_version_prefix;
+use crate::handlers::toolchain::ToolchainPin;
+use crate::handlers::{extract_component, installed_binary_path, update_after_install};
 use crate::mvr;
-use crate::paths::binaries_dir;
-use crate::types::{BinaryVersion, InstalledBinaries, Repo};
+use crate::options::Options;
+use crate::paths::{binaries_dir, release_archive_dir};
+use crate::source::{release_source, ReleaseSource};
+use crate::types::{BinaryVersion, InstalledBinaries, Provenance, Release, Repo};
 use anyhow::anyhow;
 use anyhow::bail;
 use anyhow::Error;
-use indicatif::{ProgressBar, ProgressStyle};
+use futures_util::future::join_all;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::io::Write;
 use std::time::Duration;
 
 pub fn install_binary(
@@ -26,16 +37,53 @@ pub fn install_binary(
     binary_path: PathBuf,
     yes: bool,
 ) -> Result<(), Error> {
-    let mut installed_binaries = InstalledBinaries::new()?;
-    installed_binaries.add_binary(BinaryVersion {
+    register_binary(name, &network, version, debug, binary_path, None)?;
+    update_after_install(&vec![name.to_string()], network, version, debug, yes)?;
+    Ok(())
+}
+
+/// Records a binary in the installed binaries file without changing the default version, along
+/// with its SHA-256 and the release asset it comes from
+pub fn register_binary(
+    name: &str,
+    network: &str,
+    version: &str,
+    debug: bool,
+    binary_path: PathBuf,
+    provenance: Option<Provenance>,
+) -> Result<(), Error> {
+    let mut binary = BinaryVersion {
         binary_name: name.to_string(),
-        network_release: network.clone(),
+        network_release: network.to_string(),
         version: version.to_string(),
         debug,
         path: Some(binary_path.to_string_lossy().to_string()),
-    });
+        sha256: None,
+        provenance,
+    };
+    let installed_path = installed_binary_path(&binary);
+    if installed_path.exists() {
+        binary.sha256 = Some(sha256_file(&installed_path)?);
+    }
+
+    let mut installed_binaries = InstalledBinaries::new()?;
+    // replace any previous record of the same binary
+    let previous: Vec<BinaryVersion> = installed_binaries
+        .binaries()
+        .iter()
+        .filter(|b| {
+            b.binary_name == binary.binary_name
+                && b.network_release == binary.network_release
+                && b.version == binary.version
+                && b.debug == binary.debug
+        })
+        .cloned()
+        .collect();
+    for b in &previous {
+        installed_binaries.remove_binary_version(b);
+    }
+    installed_binaries.add_binary(binary);
     installed_binaries.save_to_file()?;
-    update_after_install(&vec![name.to_string()], network, version, debug, yes)?;
     Ok(())
 }
 
@@ -47,14 +95,59 @@ pub async fn install_from_release(
     debug: bool,
     yes: bool,
     repo: Repo,
-    github_token: Option<String>,
+    options: &Options,
 ) -> Result<(), Error> {
-    let filename = match version_spec {
-        Some(version) => {
-            download_release_at_version(repo, network, &version, github_token.clone()).await?
-        }
-        None => download_latest_release(repo, network, github_token.clone()).await?,
-    };
+    let (version, added) =
+        add_from_release(name, network, version_spec, debug, repo, options).await?;
+
+    if added {
+        update_after_install(
+            &vec![name.to_string()],
+            network.to_string(),
+            &version,
+            debug,
+            yes,
+        )?;
+    } else {
+        println!("Binary {name}-{version} already installed. Use `suiup default set` to change the default binary.");
+    }
+    Ok(())
+}
+
+/// Downloads and extracts a release binary and records it as installed, without changing the
+/// default version. Returns the version and whether the binary was newly added.
+pub async fn add_from_release(
+    name: &str,
+    network: &str,
+    version_spec: Option<String>,
+    debug: bool,
+    repo: Repo,
+    options: &Options,
+) -> Result<(String, bool), Error> {
+    let asset = download_release(network, version_spec, repo, options).await?;
+    add_downloaded_release(name, network, debug, asset)
+}
+
+async fn download_release(
+    network: &str,
+    version_spec: Option<String>,
+    repo: Repo,
+    options: &Options,
+) -> Result<DownloadedAsset, Error> {
+    match version_spec {
+        Some(version) => download_release_at_version(repo, network, &version, options).await,
+        None => download_latest_release(repo, network, options).await,
+    }
+}
+
+/// Extracts a downloaded release binary and records it as installed, see [`add_from_release`]
+fn add_downloaded_release(
+    name: &str,
+    network: &str,
+    debug: bool,
+    asset: DownloadedAsset,
+) -> Result<(String, bool), Error> {
+    let filename = asset.name.clone();
 
     let version = extract_version_from_release(&filename)?;
     let binary_name = if debug && name == "sui" {
@@ -63,19 +156,448 @@ pub async fn install_from_release(
         name.to_string()
     };
 
-    if !check_if_binaries_exist(&binary_name, network.to_string(), &version)? {
-        println!("Adding binary: {name}-{version}");
-        extract_component(&binary_name, network.to_string(), &filename)?;
+    if check_if_binaries_exist(&binary_name, network.to_string(), &version)? {
+        return Ok((version, false));
+    }
+
+    println!("Adding binary: {name}-{version}");
+    extract_component(&binary_name, network.to_string(), &filename)?;
+
+    let binary_filename = format!("{}-{}", name, version);
+    #[cfg(target_os = "windows")]
+    let binary_filename = format!("{}.exe", binary_filename);
+
+    let binary_path = binaries_dir().join(network).join(binary_filename);
+    let mut provenance = asset.provenance();
+    // release lists cached by older versions do not have the tag
+    if provenance.tag.is_empty() {
+        provenance.tag = format!("{network}-{version}");
+    }
+    register_binary(
+        name,
+        network,
+        &version,
+        debug,
+        binary_path,
+        Some(provenance),
+    )?;
+    Ok((version, true))
+}
+
+/// A binary of a multi-binary install whose download is complete
+enum Downloaded {
+    Release(DownloadedAsset),
+    /// mvr releases are the binary itself, downloaded straight to the binaries folder
+    Mvr(String, Option<Provenance>),
+    /// The binary of this version is already in the binaries folder, nothing was downloaded
+    AlreadyInstalled(String),
+}
+
+/// Returns the network folder a binary is installed to: site-builder is only released for
+/// mainnet and mvr is not tied to a network
+fn install_network(name: &BinaryName, network: &str) -> String {
+    match name {
+        BinaryName::WalrusSites => "mainnet".to_string(),
+        BinaryName::Mvr => "standalone".to_string(),
+        _ => network.to_string(),
+    }
+}
 
-        let binary_filename = format!("{}-{}", name, version);
-        #[cfg(target_os = "windows")]
-        let binary_filename = format!("{}.exe", binary_filename);
+/// The release a binary of a multi-binary install is downloaded from
+enum Resolved {
+    Release(Release),
+    /// mvr releases are the binary itself, picked by version
+    Mvr {
+        version: String,
+        releases: Vec<Release>,
+    },
+}
 
-        let binary_path = binaries_dir().join(network).join(binary_filename);
-        install_binary(name, network.to_string(), &version, debug, binary_path, yes)?;
+impl Resolved {
+    fn tag(&self) -> &str {
+        match self {
+            Resolved::Release(release) => &release.tag_name,
+            Resolved::Mvr { version, .. } => version,
+        }
+    }
+}
+
+/// Finds the release to install a binary from in the release list of its repository
+async fn resolve_component(
+    component: &CommandMetadata,
+    source: &dyn ReleaseSource,
+    releases: &[Release],
+) -> Result<Resolved, Error> {
+    let CommandMetadata {
+        name,
+        network,
+        version,
+    } = component;
+    let repo = name.repo();
+    if *name == BinaryName::Mvr {
+        let version = match version {
+            Some(version) => ensure_version_prefix(version),
+            None => releases
+                .first()
+                .ok_or_else(|| anyhow!("No MVR releases found"))?
+                .tag_name
+                .clone(),
+        };
+        return Ok(Resolved::Mvr {
+            version,
+            releases: releases.to_vec(),
+        });
+    }
+    let network = install_network(name, network);
+    let release = match version {
+        Some(version) => {
+            find_release_at_version(source, &repo, releases, &network, version).await?
+        }
+        None => find_latest_release(&repo, releases, &network).await?,
+    };
+    Ok(Resolved::Release(release))
+}
+
+async fn download_component(
+    name: &BinaryName,
+    resolved: &Resolved,
+    source: &dyn ReleaseSource,
+    (os, arch): &(String, String),
+    options: &Options,
+) -> Result<Downloaded, Error> {
+    match resolved {
+        Resolved::Mvr { version, releases } => {
+            if mvr::mvr_binary_path(version).exists() {
+                return Ok(Downloaded::AlreadyInstalled(version.clone()));
+            }
+            let (version, provenance) = mvr::MvrInstaller::with_releases(releases.clone())
+                .download_version(Some(version.clone()), options)
+                .await?;
+            Ok(Downloaded::Mvr(version, provenance))
+        }
+        Resolved::Release(release) => {
+            let asset =
+                download_release_asset(source, &name.repo(), release, os, arch, options).await?;
+            Ok(Downloaded::Release(asset))
+        }
+    }
+}
+
+/// Installs several binaries at once: all archives are downloaded concurrently, then extracted
+/// and recorded one after the other. Whether to make them the default versions is asked once at
+/// the end. A failing binary does not stop the others, failures are reported together.
+pub async fn install_release_components(
+    components: &[CommandMetadata],
+    debug: bool,
+    yes: bool,
+    options: &Options,
+) -> Result<(), Error> {
+    if debug && components.iter().any(|c| c.name != BinaryName::Sui) {
+        bail!("Debug flag is only available for the `sui` binary");
+    }
+    for component in components {
+        let network = install_network(&component.name, &component.network);
+        std::fs::create_dir_all(binaries_dir().join(network))?;
+    }
+
+    println!(
+        "Downloading {}",
+        components
+            .iter()
+            .map(|c| c.name.to_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    // the release list of each repository is fetched once, concurrent fetches would race on the
+    // cached list
+    let source = release_source(options);
+    let mut releases = HashMap::new();
+    for component in components {
+        if !releases.contains_key(&component.name) {
+            let list = source.list_releases(&component.name.repo()).await;
+            releases.insert(component.name.clone(), list.map_err(|e| e.to_string()));
+        }
+    }
+
+    // binaries resolving to the same release are downloaded once, concurrent downloads of an
+    // asset would write to the same file
+    let mut resolved: Vec<(&CommandMetadata, Resolved)> = vec![];
+    let mut failed = vec![];
+    for component in components {
+        let result = match &releases[&component.name] {
+            Ok(list) => resolve_component(component, source.as_ref(), list).await,
+            Err(e) => Err(anyhow!("{e}")),
+        };
+        match result {
+            Ok(release) => {
+                if !resolved
+                    .iter()
+                    .any(|(c, r)| c.name == component.name && r.tag() == release.tag())
+                {
+                    resolved.push((component, release));
+                }
+            }
+            Err(e) => failed.push(format!("{}: {e}", component.name)),
+        }
+    }
+
+    let platform = detect_os_arch()?;
+    let options = &Options {
+        progress: Some(MultiProgress::new()),
+        ..options.clone()
+    };
+    let source = release_source(options);
+    let downloads = join_all(resolved.iter().map(|(component, release)| {
+        download_component(
+            &component.name,
+            release,
+            source.as_ref(),
+            &platform,
+            options,
+        )
+    }))
+    .await;
+
+    let mut added = vec![];
+    for ((component, _), download) in resolved.iter().zip(downloads) {
+        let name = component.name.to_str();
+        let network = install_network(&component.name, &component.network);
+        let result = download.and_then(|download| match download {
+            Downloaded::Release(asset) => add_downloaded_release(name, &network, debug, asset),
+            Downloaded::Mvr(version, provenance) => {
+                let binary_path = mvr::mvr_binary_path(&version);
+                register_binary(name, &network, &version, false, binary_path, provenance)?;
+                Ok((version, true))
+            }
+            Downloaded::AlreadyInstalled(version) => Ok((version, false)),
+        });
+        match result {
+            Ok((version, true)) => added.push((name, network, version)),
+            Ok((version, false)) => {
+                println!("Binary {name}-{version} already installed. Use `suiup default set` to change the default binary.")
+            }
+            Err(e) => failed.push(format!("{name}: {e}")),
+        }
+    }
+
+    if !added.is_empty() && confirm_set_default(yes)? {
+        for (name, network, version) in &added {
+            let debug = debug && *name == "sui";
+            update_after_install(
+                &vec![name.to_string()],
+                network.clone(),
+                version,
+                debug,
+                true,
+            )?;
+        }
+    }
+
+    if !failed.is_empty() {
+        bail!("Failed to install:\n  {}", failed.join("\n  "));
+    }
+    Ok(())
+}
+
+/// Asks once whether to make all binaries installed by a command the default versions
+fn confirm_set_default(yes: bool) -> Result<bool, Error> {
+    if yes {
+        return Ok(true);
+    }
+    loop {
+        print!("Do you want to set the new installed versions as the default ones? [y/N] ");
+        std::io::stdout().flush()?;
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        match input.trim().to_lowercase().as_str() {
+            "y" | "yes" => return Ok(true),
+            "" | "n" | "no" => {
+                println!("Keeping the current default versions.");
+                return Ok(false);
+            }
+            _ => println!("Invalid input. Please enter 'y' or 'n'."),
+        }
+    }
+}
+
+/// Where to install a release archive from, instead of discovering it in the published releases
+#[derive(Debug, Clone)]
+pub enum ArchiveOrigin {
+    File(PathBuf),
+    Url(String),
+}
+
+impl ArchiveOrigin {
+    /// Returns the file name of the archive
+    fn archive_name(&self) -> Result<String, Error> {
+        let name = match self {
+            ArchiveOrigin::File(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string()),
+            ArchiveOrigin::Url(url) => reqwest::Url::parse(url)
+                .map_err(|e| anyhow!("Invalid URL {url}: {e}"))?
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .map(String::from),
+        };
+        name.filter(|name| !name.is_empty())
+            .ok_or_else(|| anyhow!("Cannot find the archive file name in {self}"))
+    }
+}
+
+impl std::fmt::Display for ArchiveOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArchiveOrigin::File(path) => write!(f, "{}", path.display()),
+            ArchiveOrigin::Url(url) => write!(f, "{url}"),
+        }
+    }
+}
+
+/// Derives the network of a release archive from its name, e.g.
+/// `sui-testnet-v1.40.1-ubuntu-x86_64.tgz`
+fn network_from_archive_name(archive_name: &str) -> Result<String, Error> {
+    ["testnet", "devnet", "mainnet"]
+        .into_iter()
+        .find(|network| archive_name.contains(&format!("-{network}-")))
+        .map(String::from)
+        .ok_or_else(|| {
+            anyhow!("Cannot derive the network from {archive_name}, expected testnet, devnet or mainnet in the file name")
+        })
+}
+
+/// Installs a binary from a local release archive or an archive at an arbitrary URL, skipping
+/// release discovery. The network and version are derived from the archive name, which must
+/// follow the release naming, e.g. `sui-testnet-v1.40.1-ubuntu-x86_64.tgz`. An installed binary
+/// with the same version is replaced.
+pub async fn install_from_archive(
+    component: &str,
+    origin: ArchiveOrigin,
+    debug: bool,
+    yes: bool,
+    options: &Options,
+) -> Result<(), Error> {
+    let name = component.parse::<BinaryName>().map_err(|_| {
+        anyhow!("Expected a binary name such as `sui`, got `{component}`. The version is taken from the archive name")
+    })?;
+    if name == BinaryName::Mvr {
+        bail!("Installing mvr from an archive is not supported, mvr releases are not archives");
+    }
+
+    let archive_name = origin.archive_name()?;
+    let network = network_from_archive_name(&archive_name)?;
+    let version = extract_version_from_release(&archive_name)?;
+
+    // extraction reads the archive from the release archives folder
+    let archive_path = release_archive_dir().join(&archive_name);
+    let (url, asset_sha256) = match &origin {
+        ArchiveOrigin::File(path) => {
+            let path = path
+                .canonicalize()
+                .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+            if path != archive_path.canonicalize().unwrap_or_default() {
+                std::fs::copy(&path, &archive_path).map_err(|e| {
+                    anyhow!(
+                        "Cannot copy {} to {}: {e}",
+                        path.display(),
+                        archive_path.display()
+                    )
+                })?;
+            }
+            (path.display().to_string(), sha256_file(&archive_path)?)
+        }
+        ArchiveOrigin::Url(url) => {
+            // always download, the URL may serve a different build under the same name
+            if archive_path.exists() {
+                std::fs::remove_file(&archive_path)?;
+            }
+            let sha256 = download_file(url, &archive_path, &archive_name, None, options).await?;
+            (url.clone(), sha256)
+        }
+    };
+    println!("Archive SHA-256: {asset_sha256}");
+
+    let binary_name = if debug && name == BinaryName::Sui {
+        format!("{}-debug", name)
     } else {
-        println!("Binary {name}-{version} already installed. Use `suiup default set` to change the default binary.");
+        name.to_string()
+    };
+    if check_if_binaries_exist(&binary_name, network.clone(), &version)? {
+        println!("Replacing installed {binary_name}-{version} from {network}");
     }
+
+    println!("Adding binary: {name}-{version} from {origin}");
+    extract_component(&binary_name, network.clone(), &archive_name)?;
+
+    // debug builds are extracted as `sui-debug-<version>`
+    let binary_path = installed_binary_path(&BinaryVersion {
+        binary_name: name.to_string(),
+        network_release: network.clone(),
+        version: version.clone(),
+        debug,
+        path: None,
+        sha256: None,
+        provenance: None,
+    });
+    if !binary_path.exists() {
+        bail!("{archive_name} does not contain {binary_name}");
+    }
+
+    register_binary(
+        name.to_str(),
+        &network,
+        &version,
+        debug,
+        binary_path,
+        Some(Provenance {
+            tag: format!("{network}-{version}"),
+            asset: archive_name,
+            url,
+            asset_sha256,
+        }),
+    )?;
+    update_after_install(&vec![name.to_string()], network, &version, debug, yes)?;
+    Ok(())
+}
+
+/// Installs the binary for a pin (e.g. from a toolchain file or a manifest) without changing the
+/// default version
+pub async fn add_from_pin(pin: &ToolchainPin, debug: bool, options: &Options) -> Result<(), Error> {
+    let name = pin
+        .binary_name
+        .parse::<BinaryName>()
+        .map_err(|e| anyhow!(e))?;
+
+    let repo = match name {
+        BinaryName::Sui => Repo::Sui,
+        BinaryName::Walrus => Repo::Walrus,
+        BinaryName::WalrusSites => Repo::WalrusSites,
+        BinaryName::Mvr => {
+            let (version, provenance) = mvr::MvrInstaller::new()
+                .download_version(pin.version.clone(), options)
+                .await?;
+            let binary_path = mvr::mvr_binary_path(&version);
+            return register_binary(
+                &pin.binary_name,
+                &pin.network,
+                &version,
+                false,
+                binary_path,
+                provenance,
+            );
+        }
+    };
+
+    add_from_release(
+        &pin.binary_name,
+        &pin.network,
+        pin.version.clone(),
+        debug,
+        repo,
+        options,
+    )
+    .await?;
     Ok(())
 }
 
@@ -86,7 +608,11 @@ pub async fn install_from_nightly(
     branch: &str,
     debug: bool,
     yes: bool,
+    options: &Options,
 ) -> Result<(), Error> {
+    if options.offline {
+        bail!("Cannot install {name} from the {branch} branch in offline mode, it is built from the git repository");
+    }
     println!("Installing {name} from {branch} branch");
     check_cargo_rust_installed()?;
 
@@ -99,12 +625,12 @@ pub async fn install_from_nightly(
     pb.enable_steady_tick(Duration::from_millis(100));
     pb.set_message("Compiling...please wait");
 
-    let repo_url = name.repo_url();
+    let repo_url = name.repo_url(&options.github);
     let binaries_folder = binaries_dir();
     let binaries_folder_branch = binaries_folder.join(branch);
 
     let mut args = vec![
-        "install", "--locked", "--force", "--git", repo_url, "--branch", branch,
+        "install", "--locked", "--force", "--git", &repo_url, "--branch", branch,
     ];
 
     if name == &BinaryName::Walrus {
@@ -164,7 +690,11 @@ pub async fn install_from_nightly(
 }
 
 /// Install MVR CLI
-pub async fn install_mvr(version: Option<String>, yes: bool) -> Result<(), Error> {
+pub async fn install_mvr(
+    version: Option<String>,
+    yes: bool,
+    options: &Options,
+) -> Result<(), Error> {
     let network = "standalone".to_string();
     let binary_name = BinaryName::Mvr.to_string();
     if !check_if_binaries_exist(
@@ -173,19 +703,24 @@ pub async fn install_mvr(version: Option<String>, yes: bool) -> Result<(), Error
         &version.clone().unwrap_or_default(),
     )? {
         let mut installer = mvr::MvrInstaller::new();
-        let installed_version = installer.download_version(version).await?;
+        let (installed_version, provenance) = installer.download_version(version, options).await?;
 
         println!("Adding binary: mvr-{installed_version}");
 
-        let binary_path = binaries_dir()
-            .join(&network)
-            .join(format!("{}-{}", binary_name, installed_version));
-        install_binary(
+        let binary_path = mvr::mvr_binary_path(&installed_version);
+        register_binary(
             &binary_name,
-            network,
+            &network,
             &installed_version,
             false,
             binary_path,
+            provenance,
+        )?;
+        update_after_install(
+            &vec![binary_name.clone()],
+            network,
+            &installed_version,
+            false,
             yes,
         )?;
     } else {
@@ -225,3 +760,85 @@ fn check_cargo_rust_installed() -> Result<(), Error> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_name() {
+        let file = ArchiveOrigin::File(PathBuf::from("./rc/sui-testnet-v1.40.1-ubuntu-x86_64.tgz"));
+        assert_eq!(
+            file.archive_name().unwrap(),
+            "sui-testnet-v1.40.1-ubuntu-x86_64.tgz"
+        );
+        let url = ArchiveOrigin::Url(
+            "https://builds.example.com/rc/sui-devnet-v1.41.0-macos-arm64.tgz?token=x".to_string(),
+        );
+        assert_eq!(
+            url.archive_name().unwrap(),
+            "sui-devnet-v1.41.0-macos-arm64.tgz"
+        );
+        let no_name = ArchiveOrigin::Url("https://builds.example.com/".to_string());
+        assert!(no_name.archive_name().is_err());
+    }
+
+    #[test]
+    fn test_network_from_archive_name() {
+        assert_eq!(
+            network_from_archive_name("sui-testnet-v1.40.1-ubuntu-x86_64.tgz").unwrap(),
+            "testnet"
+        );
+        assert_eq!(
+            network_from_archive_name("walrus-mainnet-v1.18.2-ubuntu-x86_64.tgz").unwrap(),
+            "mainnet"
+        );
+        assert!(network_from_archive_name("sui-v1.40.1-ubuntu-x86_64.tgz").is_err());
+    }
+
+    #[test]
+    fn test_install_network() {
+        assert_eq!(install_network(&BinaryName::Sui, "devnet"), "devnet");
+        assert_eq!(install_network(&BinaryName::Walrus, "testnet"), "testnet");
+        assert_eq!(
+            install_network(&BinaryName::WalrusSites, "testnet"),
+            "mainnet"
+        );
+        assert_eq!(install_network(&BinaryName::Mvr, "testnet"), "standalone");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_component() {
+        let release = |tag: &str| Release {
+            tag_name: tag.to_string(),
+            assets: vec![crate::types::Asset {
+                name: format!("sui-{tag}-ubuntu-x86_64.tgz"),
+                ..Default::default()
+            }],
+        };
+        let releases = vec![release("testnet-v1.40.1"), release("testnet-v1.39.0")];
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = crate::source::LocalDirSource::new(temp_dir.path());
+        let component = |spec: &str| crate::commands::parse_component_with_version(spec).unwrap();
+
+        // the latest release and the same version given explicitly are one download
+        let latest = resolve_component(&component("sui"), &source, &releases)
+            .await
+            .unwrap();
+        let pinned = resolve_component(&component("sui@testnet-1.40.1"), &source, &releases)
+            .await
+            .unwrap();
+        assert_eq!(latest.tag(), "testnet-v1.40.1");
+        assert_eq!(pinned.tag(), latest.tag());
+
+        let older = resolve_component(&component("sui@testnet-1.39.0"), &source, &releases)
+            .await
+            .unwrap();
+        assert_eq!(older.tag(), "testnet-v1.39.0");
+        assert!(
+            resolve_component(&component("sui@devnet"), &source, &releases)
+                .await
+                .is_err()
+        );
+    }
+}
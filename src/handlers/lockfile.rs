@@ -0,0 +1,404 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Error};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::BinaryName;
+use crate::handlers::checksum::sha256_file;
+use crate::handlers::download::{detect_os_arch, download_asset};
+use crate::handlers::install::register_binary;
+use crate::handlers::toolchain::parse_pin;
+use crate::handlers::{extract_component, installed_binary_path, update_after_install};
+use crate::options::Options;
+use crate::paths::release_archive_dir;
+use crate::source::release_source;
+use crate::types::{Asset, BinaryVersion, InstalledBinaries, Provenance};
+
+pub const LOCK_FILE: &str = "suiup.lock";
+
+const LOCK_FILE_HEADER: &str =
+    "# This file is generated by `suiup lock`. Do not edit it manually.\n";
+
+/// Exact release assets and hashes of installed binaries, used to reproduce an installation
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct LockFile {
+    #[serde(default, rename = "binary")]
+    pub binaries: Vec<LockedBinary>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LockedBinary {
+    pub name: String,
+    pub network: String,
+    pub version: String,
+    #[serde(default)]
+    pub debug: bool,
+    /// Release tag, e.g. `testnet-v1.40.1`
+    pub tag: String,
+    /// Name of the release asset
+    pub asset: String,
+    pub url: String,
+    /// SHA-256 of the release asset
+    pub asset_sha256: String,
+    /// SHA-256 of the installed binary
+    pub sha256: String,
+}
+
+impl LockedBinary {
+    fn binary_version(&self) -> BinaryVersion {
+        BinaryVersion {
+            binary_name: self.name.clone(),
+            network_release: self.network.clone(),
+            version: self.version.clone(),
+            debug: self.debug,
+            path: None,
+            sha256: None,
+            provenance: None,
+        }
+    }
+
+    fn provenance(&self) -> Provenance {
+        Provenance {
+            tag: self.tag.clone(),
+            asset: self.asset.clone(),
+            url: self.url.clone(),
+            asset_sha256: self.asset_sha256.clone(),
+        }
+    }
+}
+
+impl LockFile {
+    /// Builds a lock file from the installed binaries. Binaries installed without a recorded
+    /// release asset (e.g. nightly builds, or installed by an older suiup) are returned apart.
+    pub fn from_installed(installed_binaries: &InstalledBinaries) -> (Self, Vec<BinaryVersion>) {
+        let mut binaries = vec![];
+        let mut skipped = vec![];
+        for binary in installed_binaries.binaries() {
+            match (&binary.provenance, &binary.sha256) {
+                (Some(provenance), Some(sha256)) => binaries.push(LockedBinary {
+                    name: binary.binary_name.clone(),
+                    network: binary.network_release.clone(),
+                    version: binary.version.clone(),
+                    debug: binary.debug,
+                    tag: provenance.tag.clone(),
+                    asset: provenance.asset.clone(),
+                    url: provenance.url.clone(),
+                    asset_sha256: provenance.asset_sha256.clone(),
+                    sha256: sha256.clone(),
+                }),
+                _ => skipped.push(binary.clone()),
+            }
+        }
+        binaries.sort_by(|a, b| {
+            (&a.name, &a.network, &a.version, a.debug)
+                .cmp(&(&b.name, &b.network, &b.version, b.debug))
+        });
+        (LockFile { binaries }, skipped)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read lock file {}: {e}", path.display()))?;
+        toml::from_str(&content)
+            .map_err(|e| anyhow!("Cannot parse lock file {}: {e}", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), Error> {
+        let content =
+            toml::to_string_pretty(self).map_err(|e| anyhow!("Cannot serialize lock file: {e}"))?;
+        std::fs::write(path, format!("{LOCK_FILE_HEADER}{content}"))
+            .map_err(|e| anyhow!("Cannot write lock file {}: {e}", path.display()))
+    }
+
+    /// Returns the entries matching a binary spec such as `sui`, `sui@testnet` or
+    /// `sui@testnet-1.40.1`, or all entries if no spec is given
+    pub fn select(&self, spec: Option<&str>, debug: bool) -> Result<Vec<&LockedBinary>, Error> {
+        let Some(spec) = spec else {
+            return Ok(self.binaries.iter().collect());
+        };
+
+        let (name, pin) = match spec.split_once('@') {
+            Some((name, version_spec)) => (name, Some(parse_pin(name, version_spec)?)),
+            None => (spec, None),
+        };
+
+        Ok(self
+            .binaries
+            .iter()
+            .filter(|b| b.name == name && b.debug == debug)
+            .filter(|b| {
+                pin.as_ref().is_none_or(|pin| {
+                    b.network == pin.network && pin.version.as_ref().is_none_or(|v| &b.version == v)
+                })
+            })
+            .collect())
+    }
+}
+
+/// Walks up from `start` and returns the first lock file found
+pub fn find_lock_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(LOCK_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Handles the `lock` command: writes the lock file for the installed binaries
+pub fn handle_lock(path: &Path) -> Result<(), Error> {
+    let (lock, skipped) = LockFile::from_installed(&InstalledBinaries::new()?);
+    for binary in skipped {
+        println!(
+            "WARNING: no release asset recorded for {binary} from {}, not locking it. Reinstall it to record one",
+            binary.network_release
+        );
+    }
+    lock.write(path)?;
+    println!(
+        "Locked {} binaries in {}",
+        lock.binaries.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+fn check_hash(path: &Path, expected: &str, what: &str) -> Result<(), Error> {
+    let actual = sha256_file(path)?;
+    if actual != expected {
+        std::fs::remove_file(path)?;
+        bail!("SHA-256 mismatch for {what}: expected {expected}, got {actual}. Refusing to install it");
+    }
+    Ok(())
+}
+
+/// Downloads the release asset of a locked binary from the release source, e.g. a mirror,
+/// verifying its SHA-256
+async fn download_locked_asset(
+    locked: &LockedBinary,
+    download_to: &PathBuf,
+    options: &Options,
+) -> Result<(), Error> {
+    let repo = locked
+        .name
+        .parse::<BinaryName>()
+        .map_err(|e| anyhow!(e))?
+        .repo();
+    let asset = Asset {
+        browser_download_url: locked.url.clone(),
+        name: locked.asset.clone(),
+        ..Default::default()
+    };
+    let source = release_source(options);
+    download_asset(
+        source.as_ref(),
+        &repo,
+        &asset,
+        download_to,
+        Some(&locked.asset_sha256),
+        options,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Installs a locked binary, verifying the asset and binary hashes. Returns whether the binary
+/// was newly installed.
+async fn install_locked_binary(locked: &LockedBinary, options: &Options) -> Result<bool, Error> {
+    let binary = locked.binary_version();
+    let binary_path = installed_binary_path(&binary);
+
+    if binary_path.exists() {
+        let actual = sha256_file(&binary_path)?;
+        if actual != locked.sha256 {
+            bail!(
+                "Installed {binary} from {} has SHA-256 {actual}, but {LOCK_FILE} expects {}",
+                locked.network,
+                locked.sha256
+            );
+        }
+        println!(
+            "{binary} from {} is installed and matches {LOCK_FILE}",
+            locked.network
+        );
+        register_binary(
+            &locked.name,
+            &locked.network,
+            &locked.version,
+            locked.debug,
+            binary_path,
+            Some(locked.provenance()),
+        )?;
+        return Ok(false);
+    }
+
+    let (os, arch) = detect_os_arch()?;
+    if !(locked.asset.contains(&os) && locked.asset.contains(&arch)) {
+        bail!(
+            "{LOCK_FILE} records {} for {binary}, which is not built for {os}-{arch}",
+            locked.asset
+        );
+    }
+
+    if locked.network == "standalone" {
+        // standalone assets are the binary itself
+        if let Some(parent) = binary_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        download_locked_asset(locked, &binary_path, options).await?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&binary_path, std::fs::Permissions::from_mode(0o755))?;
+        }
+    } else {
+        let archive_path = release_archive_dir().join(&locked.asset);
+        download_locked_asset(locked, &archive_path, options).await?;
+
+        let binary_name = if locked.debug {
+            format!("{}-debug", locked.name)
+        } else {
+            locked.name.clone()
+        };
+        extract_component(&binary_name, locked.network.clone(), &locked.asset)?;
+        if !binary_path.exists() {
+            bail!("{} does not contain {binary_name}", locked.asset);
+        }
+    }
+    check_hash(&binary_path, &locked.sha256, &binary.to_string())?;
+
+    register_binary(
+        &locked.name,
+        &locked.network,
+        &locked.version,
+        locked.debug,
+        binary_path,
+        Some(locked.provenance()),
+    )?;
+    Ok(true)
+}
+
+/// Handles `install --locked`: installs the binaries recorded in the nearest `suiup.lock`,
+/// refusing any asset or binary whose hash differs from the lock file
+pub async fn install_locked(
+    spec: Option<&str>,
+    debug: bool,
+    yes: bool,
+    options: &Options,
+) -> Result<(), Error> {
+    let path = find_lock_file(&std::env::current_dir()?).ok_or_else(|| {
+        anyhow!("No {LOCK_FILE} found in the current directory or its parents. Run `suiup lock` to create one")
+    })?;
+    let lock = LockFile::read(&path)?;
+    let selected = lock.select(spec, debug)?;
+
+    match (spec, selected.len()) {
+        (Some(spec), 0) => bail!("{spec} is not in {}", path.display()),
+        (None, 0) => bail!("{} does not contain any binary", path.display()),
+        (Some(spec), n) if n > 1 => bail!(
+            "{spec} matches {n} binaries in {}. Use e.g. `{spec}@testnet-1.40.1` to pick one",
+            path.display()
+        ),
+        _ => {}
+    }
+
+    println!("Installing from {}", path.display());
+    for locked in selected {
+        if install_locked_binary(locked, options).await? {
+            update_after_install(
+                &vec![locked.name.clone()],
+                locked.network.clone(),
+                &locked.version,
+                locked.debug,
+                yes,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed_binaries() -> InstalledBinaries {
+        serde_json::from_value(serde_json::json!({
+            "binaries": [
+                {
+                    "binary_name": "sui",
+                    "network_release": "testnet",
+                    "version": "v1.40.1",
+                    "debug": false,
+                    "path": null,
+                    "sha256": "b1",
+                    "provenance": {
+                        "tag": "testnet-v1.40.1",
+                        "asset": "sui-testnet-v1.40.1-ubuntu-x86_64.tgz",
+                        "url": "https://github.com/MystenLabs/sui/releases/download/testnet-v1.40.1/sui-testnet-v1.40.1-ubuntu-x86_64.tgz",
+                        "asset_sha256": "a1"
+                    }
+                },
+                {
+                    "binary_name": "sui",
+                    "network_release": "devnet",
+                    "version": "v1.41.0",
+                    "debug": false,
+                    "path": null,
+                    "sha256": "b2",
+                    "provenance": {
+                        "tag": "devnet-v1.41.0",
+                        "asset": "sui-devnet-v1.41.0-ubuntu-x86_64.tgz",
+                        "url": "https://example.com/sui-devnet-v1.41.0-ubuntu-x86_64.tgz",
+                        "asset_sha256": "a2"
+                    }
+                },
+                {
+                    "binary_name": "sui",
+                    "network_release": "main",
+                    "version": "nightly",
+                    "debug": false,
+                    "path": null
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_lock_file_from_installed() {
+        let (lock, skipped) = LockFile::from_installed(&installed_binaries());
+        assert_eq!(lock.binaries.len(), 2);
+        assert_eq!(lock.binaries[0].network, "devnet");
+        assert_eq!(lock.binaries[1].sha256, "b1");
+        assert_eq!(lock.binaries[1].asset_sha256, "a1");
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].version, "nightly");
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(LOCK_FILE);
+        lock.write(&path).unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .starts_with(LOCK_FILE_HEADER));
+        assert_eq!(LockFile::read(&path).unwrap(), lock);
+        assert_eq!(find_lock_file(temp_dir.path()), Some(path));
+    }
+
+    #[test]
+    fn test_lock_file_select() {
+        let (lock, _) = LockFile::from_installed(&installed_binaries());
+        assert_eq!(lock.select(None, false).unwrap().len(), 2);
+        assert_eq!(lock.select(Some("sui"), false).unwrap().len(), 2);
+        assert_eq!(lock.select(Some("sui"), true).unwrap().len(), 0);
+        assert_eq!(lock.select(Some("walrus"), false).unwrap().len(), 0);
+
+        let selected = lock.select(Some("sui@testnet"), false).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].version, "v1.40.1");
+
+        let selected = lock.select(Some("sui@devnet-1.41.0"), false).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].network, "devnet");
+    }
+}
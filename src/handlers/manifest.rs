@@ -0,0 +1,438 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Error};
+use serde::Deserialize;
+
+use crate::handlers::install::add_from_pin;
+use crate::handlers::installed_binary_path;
+use crate::handlers::switch::{get_binary_destination_path, switch_to_binary};
+use crate::handlers::toolchain::{parse_pin, ToolchainPin};
+use crate::handlers::version::compare_versions;
+use crate::options::Options;
+use crate::paths::default_file_path;
+use crate::types::{BinaryVersion, InstalledBinaries, Version};
+
+/// Desired state of the installed binaries, read from a manifest such as `tools.toml`:
+///
+/// ```toml
+/// prune = true
+///
+/// [[binary]]
+/// name = "sui"
+/// version = "testnet-v1.40.1"
+/// default = true
+///
+/// [[binary]]
+/// name = "walrus"
+/// version = "testnet"
+/// ```
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// Remove installed binaries that are not listed in the manifest
+    #[serde(default)]
+    pub prune: bool,
+    #[serde(default, rename = "binary")]
+    pub binaries: Vec<ManifestEntry>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestEntry {
+    pub name: String,
+    /// Same format as `suiup install`, e.g. `testnet`, `testnet-v1.40.1`, `1.40.1` (defaults to
+    /// `testnet`). With a network only, any installed version for it satisfies the entry.
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub debug: bool,
+    /// Make this binary the default one
+    #[serde(default)]
+    pub default: bool,
+}
+
+/// A desired binary from the manifest, resolved to a network and an optional version
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesiredBinary {
+    pub pin: ToolchainPin,
+    pub debug: bool,
+}
+
+impl DesiredBinary {
+    fn matches(&self, binary: &BinaryVersion) -> bool {
+        binary.binary_name == self.pin.binary_name
+            && binary.network_release == self.pin.network
+            && binary.debug == self.debug
+            && self
+                .pin
+                .version
+                .as_ref()
+                .is_none_or(|v| &binary.version == v)
+    }
+
+    /// Returns the latest installed binary satisfying this entry
+    fn resolve(&self, installed_binaries: &InstalledBinaries) -> Option<BinaryVersion> {
+        installed_binaries
+            .binaries()
+            .iter()
+            .filter(|b| self.matches(b))
+            .max_by(|a, b| compare_versions(&a.version, &b.version))
+            .cloned()
+    }
+}
+
+impl Display for DesiredBinary {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}@{}", self.pin.binary_name, self.pin.network)?;
+        if let Some(version) = &self.pin.version {
+            write!(f, "-{version}")?;
+        }
+        if self.debug {
+            write!(f, " (debug build)")?;
+        }
+        Ok(())
+    }
+}
+
+/// A step needed to bring the installed binaries to the state described by the manifest
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanAction {
+    Install(DesiredBinary),
+    SetDefault(DesiredBinary),
+    Remove(BinaryVersion),
+}
+
+impl Display for PlanAction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PlanAction::Install(binary) => write!(f, "+ install {binary}"),
+            PlanAction::SetDefault(binary) => write!(f, "* set default {binary}"),
+            PlanAction::Remove(binary) => {
+                write!(f, "- remove {binary} from {}", binary.network_release)
+            }
+        }
+    }
+}
+
+impl Manifest {
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read manifest {}: {e}", path.display()))?;
+        toml::from_str(&content)
+            .map_err(|e| anyhow!("Cannot parse manifest {}: {e}", path.display()))
+    }
+
+    /// Resolves the manifest entries, checking that there is at most one default per binary
+    fn desired(&self) -> Result<Vec<(DesiredBinary, bool)>, Error> {
+        let mut defaults = HashSet::new();
+        self.binaries
+            .iter()
+            .map(|entry| {
+                if entry.debug && entry.name != "sui" {
+                    bail!(
+                        "Debug builds are only available for sui, not {}",
+                        entry.name
+                    );
+                }
+                if entry.default && !defaults.insert(entry.name.clone()) {
+                    bail!("More than one default {} in the manifest", entry.name);
+                }
+                let pin = parse_pin(&entry.name, entry.version.as_deref().unwrap_or("testnet"))?;
+                Ok((
+                    DesiredBinary {
+                        pin,
+                        debug: entry.debug,
+                    },
+                    entry.default,
+                ))
+            })
+            .collect()
+    }
+}
+
+fn read_default_binaries() -> Result<BTreeMap<String, (String, Version, bool)>, Error> {
+    let path = default_file_path()?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow!("Cannot read file {}: {e}", path.display()))?;
+    serde_json::from_str(&content)
+        .map_err(|_| anyhow!("Cannot decode default binary file to JSON. Is the file corrupted?"))
+}
+
+/// Computes the steps to go from the installed and default binaries to the manifest's state.
+/// Installs come first, then default changes, then removals.
+pub fn compute_plan(
+    manifest: &Manifest,
+    installed_binaries: &InstalledBinaries,
+    default_binaries: &BTreeMap<String, (String, Version, bool)>,
+) -> Result<Vec<PlanAction>, Error> {
+    let desired = manifest.desired()?;
+
+    let mut installs = vec![];
+    let mut defaults = vec![];
+    for (binary, is_default) in &desired {
+        let installed = binary.resolve(installed_binaries);
+        if installed.is_none() {
+            installs.push(PlanAction::Install(binary.clone()));
+        }
+        if !is_default {
+            continue;
+        }
+
+        let current = default_binaries.get(&binary.pin.binary_name);
+        let up_to_date = current.is_some_and(|(network, version, debug)| {
+            *network == binary.pin.network
+                && *debug == binary.debug
+                && binary.pin.version.as_ref().is_none_or(|v| v == version)
+        });
+        if !up_to_date || installed.is_none() {
+            defaults.push(PlanAction::SetDefault(binary.clone()));
+        }
+    }
+
+    let mut removals = vec![];
+    if manifest.prune {
+        for binary in installed_binaries.binaries() {
+            if !desired.iter().any(|(d, _)| d.matches(binary)) {
+                removals.push(PlanAction::Remove(binary.clone()));
+            }
+        }
+    }
+
+    Ok(installs
+        .into_iter()
+        .chain(defaults)
+        .chain(removals)
+        .collect())
+}
+
+/// Removes a single installed binary. If it is still the default one, the default entry and its
+/// shim are removed too.
+fn remove_installed_binary(binary: &BinaryVersion) -> Result<(), Error> {
+    let path = installed_binary_path(binary);
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .map_err(|e| anyhow!("Cannot remove {}: {e}", path.display()))?;
+    }
+
+    let mut installed_binaries = InstalledBinaries::new()?;
+    installed_binaries.remove_binary_version(binary);
+    installed_binaries.save_to_file()?;
+
+    let mut default_binaries = read_default_binaries()?;
+    let is_default =
+        default_binaries
+            .get(&binary.binary_name)
+            .is_some_and(|(network, version, debug)| {
+                *network == binary.network_release
+                    && *version == binary.version
+                    && *debug == binary.debug
+            });
+    if is_default {
+        let shim = get_binary_destination_path(binary);
+        if shim.exists() {
+            std::fs::remove_file(&shim)
+                .map_err(|e| anyhow!("Cannot remove {}: {e}", shim.display()))?;
+        }
+        default_binaries.remove(&binary.binary_name);
+        std::fs::write(
+            default_file_path()?,
+            serde_json::to_string_pretty(&default_binaries)?,
+        )?;
+    }
+    Ok(())
+}
+
+fn load_plan(path: &Path) -> Result<Vec<PlanAction>, Error> {
+    let manifest = Manifest::read(path)?;
+    compute_plan(
+        &manifest,
+        &InstalledBinaries::new()?,
+        &read_default_binaries()?,
+    )
+}
+
+fn print_plan(plan: &[PlanAction]) {
+    if plan.is_empty() {
+        println!("Nothing to do, the installed binaries match the manifest");
+        return;
+    }
+    println!("\x1b[1mPlan:\x1b[0m");
+    for action in plan {
+        println!("  {action}");
+    }
+}
+
+/// Handles the `plan` command
+pub fn handle_plan(path: &Path) -> Result<(), Error> {
+    print_plan(&load_plan(path)?);
+    Ok(())
+}
+
+/// Handles the `apply` command: prints the plan, then executes it without prompting
+pub async fn handle_apply(path: &Path, options: &Options) -> Result<(), Error> {
+    let plan = load_plan(path)?;
+    print_plan(&plan);
+
+    for action in &plan {
+        match action {
+            PlanAction::Install(binary) => {
+                println!("Installing {binary}");
+                add_from_pin(&binary.pin, binary.debug, options).await?;
+            }
+            PlanAction::SetDefault(desired) => {
+                let binary = desired
+                    .resolve(&InstalledBinaries::new()?)
+                    .ok_or_else(|| anyhow!("Cannot find {desired} after installing it"))?;
+                switch_to_binary(&binary)?;
+                println!("[{}] {binary} set as default", binary.network_release);
+            }
+            PlanAction::Remove(binary) => {
+                remove_installed_binary(binary)?;
+                println!("Removed {binary} from {}", binary.network_release);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binary(name: &str, network: &str, version: &str) -> BinaryVersion {
+        BinaryVersion {
+            binary_name: name.to_string(),
+            network_release: network.to_string(),
+            version: version.to_string(),
+            debug: false,
+            path: None,
+            sha256: None,
+            provenance: None,
+        }
+    }
+
+    fn installed(binaries: Vec<BinaryVersion>) -> InstalledBinaries {
+        serde_json::from_value(serde_json::json!({ "binaries": binaries })).unwrap()
+    }
+
+    #[test]
+    fn test_compute_plan() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+prune = true
+
+[[binary]]
+name = "sui"
+version = "testnet-1.40.1"
+default = true
+
+[[binary]]
+name = "walrus"
+version = "testnet"
+"#,
+        )
+        .unwrap();
+
+        let installed = installed(vec![
+            binary("sui", "testnet", "v1.39.3"),
+            binary("walrus", "testnet", "v1.18.2"),
+        ]);
+        let defaults = BTreeMap::from([(
+            "sui".to_string(),
+            ("testnet".to_string(), "v1.39.3".to_string(), false),
+        )]);
+
+        let plan = compute_plan(&manifest, &installed, &defaults).unwrap();
+        let sui = DesiredBinary {
+            pin: ToolchainPin {
+                binary_name: "sui".to_string(),
+                network: "testnet".to_string(),
+                version: Some("v1.40.1".to_string()),
+            },
+            debug: false,
+        };
+        assert_eq!(
+            plan,
+            vec![
+                PlanAction::Install(sui.clone()),
+                PlanAction::SetDefault(sui),
+                PlanAction::Remove(binary("sui", "testnet", "v1.39.3")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compute_plan_up_to_date() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[[binary]]
+name = "sui"
+version = "testnet"
+default = true
+"#,
+        )
+        .unwrap();
+        let installed = installed(vec![
+            binary("sui", "testnet", "v1.40.1"),
+            binary("sui", "devnet", "v1.41.0"),
+        ]);
+        let defaults = BTreeMap::from([(
+            "sui".to_string(),
+            ("testnet".to_string(), "v1.40.1".to_string(), false),
+        )]);
+        // without prune, extra binaries are kept
+        assert!(compute_plan(&manifest, &installed, &defaults)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_resolve_latest_installed_version() {
+        let desired = DesiredBinary {
+            pin: parse_pin("walrus", "testnet").unwrap(),
+            debug: false,
+        };
+        let installed = installed(vec![
+            binary("walrus", "testnet", "v1.9.0"),
+            binary("walrus", "testnet", "v1.10.0"),
+            binary("walrus", "devnet", "v1.11.0"),
+        ]);
+        assert_eq!(
+            desired.resolve(&installed),
+            Some(binary("walrus", "testnet", "v1.10.0"))
+        );
+    }
+
+    #[test]
+    fn test_manifest_validation() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+[[binary]]
+name = "sui"
+default = true
+
+[[binary]]
+name = "sui"
+version = "devnet"
+default = true
+"#,
+        )
+        .unwrap();
+        assert!(compute_plan(&manifest, &installed(vec![]), &BTreeMap::new()).is_err());
+
+        let manifest: Manifest = toml::from_str(
+            r#"
+[[binary]]
+name = "walrus"
+debug = true
+"#,
+        )
+        .unwrap();
+        assert!(compute_plan(&manifest, &installed(vec![]), &BTreeMap::new()).is_err());
+    }
+}
@@ -0,0 +1,475 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! `suiup mirror sync`: builds a release mirror for hosts without access to GitHub, laid out as
+//! `<repo>/releases.json` and `<repo>/<asset>` so that it can be used with `--mirror`.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::BinaryName;
+use crate::handlers::checksum::sha256_file;
+use crate::handlers::download::{download_asset, download_verified_asset};
+use crate::handlers::signature::SIGNATURE_EXTENSION;
+use crate::handlers::version::{parse_version, VersionNumber};
+use crate::options::Options;
+use crate::paths::release_archive_dir;
+use crate::source::{GithubSource, ReleaseSource, RELEASES_FILE};
+use crate::types::{Asset, Release, Repo};
+
+/// Index of the assets in a mirror, with their SHA-256
+pub const INDEX_FILE: &str = "index.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+/// A version requirement made of comma separated comparisons, e.g. `>=1.40.0, <1.45.0`. An
+/// empty requirement matches every version.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionReq {
+    comparators: Vec<(Op, VersionNumber)>,
+}
+
+impl FromStr for VersionReq {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut comparators = vec![];
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            if part == "*" {
+                continue;
+            }
+            let (op, version) = [
+                (">=", Op::Ge),
+                ("<=", Op::Le),
+                (">", Op::Gt),
+                ("<", Op::Lt),
+                ("=", Op::Eq),
+            ]
+            .into_iter()
+            .find_map(|(prefix, op)| part.strip_prefix(prefix).map(|v| (op, v)))
+            .unwrap_or((Op::Eq, part));
+            let version =
+                parse_version(version).ok_or_else(|| format!("Invalid version in `{part}`"))?;
+            comparators.push((op, version));
+        }
+        Ok(Self { comparators })
+    }
+}
+
+impl VersionReq {
+    pub fn matches(&self, version: &VersionNumber) -> bool {
+        self.comparators.iter().all(|(op, v)| match op {
+            Op::Eq => version == v,
+            Op::Gt => version > v,
+            Op::Ge => version >= v,
+            Op::Lt => version < v,
+            Op::Le => version <= v,
+        })
+    }
+}
+
+/// Returns the version of a release from its tag, e.g. `testnet-v1.40.1` or `v0.0.5`
+fn release_version(tag: &str) -> Option<VersionNumber> {
+    let version = tag.rsplit('-').next()?.strip_prefix('v')?;
+    if version.split('.').count() != 3 {
+        return None;
+    }
+    parse_version(version)
+}
+
+/// What to copy into a mirror
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    pub binaries: Vec<BinaryName>,
+    /// Networks of the releases to mirror. Standalone binaries (mvr) are not tied to a network.
+    pub networks: Vec<String>,
+    pub versions: VersionReq,
+    /// Target platforms as `<os>-<arch>`, e.g. `ubuntu-x86_64`
+    pub platforms: Vec<String>,
+}
+
+impl SyncOptions {
+    fn matches_release(&self, repo: &Repo, release: &Release) -> bool {
+        let Some(version) = release_version(&release.tag_name) else {
+            return false;
+        };
+        let network_matches = matches!(repo, Repo::Mvr)
+            || self.networks.iter().any(|network| {
+                release
+                    .assets
+                    .iter()
+                    .any(|a| a.name.contains(network.as_str()))
+            });
+        network_matches && self.versions.matches(&version)
+    }
+
+    fn matches_asset(&self, asset: &Asset) -> bool {
+        !is_sidecar(&asset.name)
+            && self.platforms.iter().any(|platform| {
+                let (os, arch) = platform.split_once('-').unwrap_or((platform.as_str(), ""));
+                asset.name.contains(os) && asset.name.contains(arch)
+            })
+    }
+}
+
+/// Whether an asset is a checksum or signature file published next to another asset
+fn is_sidecar(name: &str) -> bool {
+    [".sha256", ".sha256sum", ".minisig"]
+        .iter()
+        .any(|ext| name.ends_with(ext))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IndexEntry {
+    pub repo: String,
+    pub tag: String,
+    pub asset: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// The index of a mirror, listing every mirrored asset with its SHA-256
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct MirrorIndex {
+    #[serde(default)]
+    pub assets: Vec<IndexEntry>,
+}
+
+impl MirrorIndex {
+    pub fn read(dir: &Path) -> Result<Self, Error> {
+        let path = dir.join(INDEX_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+        serde_json::from_str(&content).map_err(|e| anyhow!("Cannot parse {}: {e}", path.display()))
+    }
+
+    pub fn write(&mut self, dir: &Path) -> Result<(), Error> {
+        self.assets
+            .sort_by(|a, b| (&a.repo, &a.tag, &a.asset).cmp(&(&b.repo, &b.tag, &b.asset)));
+        let path = dir.join(INDEX_FILE);
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .map_err(|e| anyhow!("Cannot write {}: {e}", path.display()))
+    }
+
+    fn find(&self, repo: &Repo, asset: &str) -> Option<&IndexEntry> {
+        let repo = repo.to_string();
+        self.assets
+            .iter()
+            .find(|e| e.repo == repo && e.asset == asset)
+    }
+
+    fn upsert(&mut self, entry: IndexEntry) {
+        self.assets
+            .retain(|e| !(e.repo == entry.repo && e.asset == entry.asset));
+        self.assets.push(entry);
+    }
+}
+
+/// Merges newly mirrored releases into the releases already in the mirror, newest first
+fn merge_releases(existing: Vec<Release>, synced: Vec<Release>) -> Vec<Release> {
+    let mut merged = existing;
+    for release in synced {
+        match merged.iter_mut().find(|r| r.tag_name == release.tag_name) {
+            Some(current) => {
+                for asset in release.assets {
+                    current.assets.retain(|a| a.name != asset.name);
+                    current.assets.push(asset);
+                }
+            }
+            None => merged.push(release),
+        }
+    }
+    merged.sort_by_key(|r| std::cmp::Reverse(release_version(&r.tag_name)));
+    merged
+}
+
+/// An asset as listed in the mirror's `releases.json`. Mirrors resolve assets by name, the URL
+/// is informative only.
+fn mirror_asset(repo: &Repo, name: &str) -> Asset {
+    Asset {
+        browser_download_url: format!("{repo}/{name}"),
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Copies an asset into the mirror unless the mirror already has it. The asset is downloaded
+/// and verified in the release archives folder first, reusing archives that are already there.
+async fn sync_asset(
+    source: &dyn ReleaseSource,
+    repo: &Repo,
+    release: &Release,
+    asset: &Asset,
+    repo_dir: &Path,
+    index: &mut MirrorIndex,
+    options: &Options,
+) -> Result<String, Error> {
+    let destination = repo_dir.join(&asset.name);
+    if let Some(entry) = index.find(repo, &asset.name) {
+        if destination.exists() && sha256_file(&destination)? == entry.sha256 {
+            println!("{} is up to date", asset.name);
+            return Ok(entry.sha256.clone());
+        }
+    }
+
+    let archive = release_archive_dir().join(&asset.name);
+    let sha256 =
+        download_verified_asset(source, repo, &release.assets, asset, &archive, options).await?;
+    std::fs::copy(&archive, &destination)
+        .map_err(|e| anyhow!("Cannot copy {} to the mirror: {e}", asset.name))?;
+    index.upsert(IndexEntry {
+        repo: repo.to_string(),
+        tag: release.tag_name.clone(),
+        asset: asset.name.clone(),
+        size: destination.metadata()?.len(),
+        sha256: sha256.clone(),
+    });
+    Ok(sha256)
+}
+
+/// Mirrors the matching releases of one repository and returns the number of mirrored assets
+async fn sync_repo(
+    source: &dyn ReleaseSource,
+    repo: &Repo,
+    dir: &Path,
+    sync_options: &SyncOptions,
+    index: &mut MirrorIndex,
+    options: &Options,
+) -> Result<usize, Error> {
+    let repo_dir = dir.join(repo.to_string());
+    std::fs::create_dir_all(&repo_dir)?;
+
+    println!("Fetching releases of {repo}...");
+    let releases = source.list_releases(repo).await?;
+    let mut synced = vec![];
+    let mut count = 0;
+
+    for release in releases
+        .iter()
+        .filter(|r| sync_options.matches_release(repo, r))
+    {
+        let assets: Vec<&Asset> = release
+            .assets
+            .iter()
+            .filter(|a| sync_options.matches_asset(a))
+            .collect();
+        if assets.is_empty() {
+            continue;
+        }
+
+        let mut mirrored = Release {
+            tag_name: release.tag_name.clone(),
+            assets: vec![],
+        };
+        for asset in assets {
+            let sha256 =
+                sync_asset(source, repo, release, asset, &repo_dir, index, options).await?;
+            mirrored.assets.push(mirror_asset(repo, &asset.name));
+
+            let checksum_name = format!("{}.sha256", asset.name);
+            std::fs::write(
+                repo_dir.join(&checksum_name),
+                format!("{sha256}  {}\n", asset.name),
+            )?;
+            mirrored.assets.push(mirror_asset(repo, &checksum_name));
+
+            let signature_name = format!("{}.{SIGNATURE_EXTENSION}", asset.name);
+            if let Some(signature) = release.assets.iter().find(|a| a.name == signature_name) {
+                download_asset(
+                    source,
+                    repo,
+                    signature,
+                    &repo_dir.join(&signature_name),
+                    None,
+                    options,
+                )
+                .await?;
+                mirrored.assets.push(mirror_asset(repo, &signature_name));
+            }
+            count += 1;
+        }
+        synced.push(mirrored);
+    }
+
+    let releases_file = repo_dir.join(RELEASES_FILE);
+    let existing: Vec<Release> = if releases_file.exists() {
+        serde_json::from_str(&std::fs::read_to_string(&releases_file)?)
+            .map_err(|e| anyhow!("Cannot parse {}: {e}", releases_file.display()))?
+    } else {
+        vec![]
+    };
+    std::fs::write(
+        &releases_file,
+        serde_json::to_string_pretty(&merge_releases(existing, synced))?,
+    )?;
+    Ok(count)
+}
+
+/// Handles `mirror sync`: downloads the releases matching `sync_options` from GitHub into a mirror
+/// directory. Assets already in the mirror with the SHA-256 recorded in the index are not
+/// downloaded again.
+pub async fn handle_mirror_sync(
+    dir: &Path,
+    sync_options: &SyncOptions,
+    options: &Options,
+) -> Result<(), Error> {
+    // a configured mirror may be the directory being synced, so it is never read from
+    if options.offline {
+        bail!("`mirror sync` downloads from GitHub and cannot run in offline mode");
+    }
+    std::fs::create_dir_all(dir)
+        .map_err(|e| anyhow!("Cannot create mirror directory {}: {e}", dir.display()))?;
+    let source = GithubSource::new(options);
+    let mut index = MirrorIndex::read(dir)?;
+
+    for binary in &sync_options.binaries {
+        let repo = binary.repo();
+        let result = sync_repo(&source, &repo, dir, sync_options, &mut index, options).await;
+        // keep the index of what was mirrored so far, so that a retry resumes from there
+        index.write(dir)?;
+        let count = result?;
+        println!("Mirrored {count} assets of {repo}");
+    }
+
+    println!("Mirror in {} is up to date", dir.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, assets: &[&str]) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            assets: assets
+                .iter()
+                .map(|name| Asset {
+                    browser_download_url: format!("https://example.com/{name}"),
+                    name: name.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_version_req() {
+        let req: VersionReq = ">=1.40.0, <1.45".parse().unwrap();
+        assert!(req.matches(&[1, 40, 0]));
+        assert!(req.matches(&[1, 44, 9]));
+        assert!(!req.matches(&[1, 45, 0]));
+        assert!(!req.matches(&[1, 39, 3]));
+
+        let exact: VersionReq = "v1.40.1".parse().unwrap();
+        assert!(exact.matches(&[1, 40, 1]));
+        assert!(!exact.matches(&[1, 40, 2]));
+
+        assert!(VersionReq::default().matches(&[0, 0, 1]));
+        assert!("*".parse::<VersionReq>().unwrap().matches(&[9, 9, 9]));
+        assert!(">=1.x".parse::<VersionReq>().is_err());
+    }
+
+    #[test]
+    fn test_release_version() {
+        assert_eq!(release_version("testnet-v1.40.1"), Some([1, 40, 1]));
+        assert_eq!(release_version("v0.0.5"), Some([0, 0, 5]));
+        assert_eq!(release_version("nightly"), None);
+    }
+
+    #[test]
+    fn test_sync_options() {
+        let options = SyncOptions {
+            binaries: vec![BinaryName::Sui],
+            networks: vec!["testnet".to_string()],
+            versions: ">=1.40.0".parse().unwrap(),
+            platforms: vec!["ubuntu-x86_64".to_string()],
+        };
+        let testnet = release(
+            "testnet-v1.40.1",
+            &[
+                "sui-testnet-v1.40.1-ubuntu-x86_64.tgz",
+                "sui-testnet-v1.40.1-ubuntu-x86_64.tgz.minisig",
+                "sui-testnet-v1.40.1-macos-arm64.tgz",
+            ],
+        );
+        assert!(options.matches_release(&Repo::Sui, &testnet));
+        let selected: Vec<_> = testnet
+            .assets
+            .iter()
+            .filter(|a| options.matches_asset(a))
+            .map(|a| a.name.as_str())
+            .collect();
+        assert_eq!(selected, vec!["sui-testnet-v1.40.1-ubuntu-x86_64.tgz"]);
+
+        let devnet = release("devnet-v1.41.0", &["sui-devnet-v1.41.0-ubuntu-x86_64.tgz"]);
+        assert!(!options.matches_release(&Repo::Sui, &devnet));
+        let old = release(
+            "testnet-v1.39.0",
+            &["sui-testnet-v1.39.0-ubuntu-x86_64.tgz"],
+        );
+        assert!(!options.matches_release(&Repo::Sui, &old));
+        // mvr releases are not tied to a network
+        let mvr = release("v1.40.0", &["mvr-ubuntu-x86_64"]);
+        assert!(options.matches_release(&Repo::Mvr, &mvr));
+    }
+
+    #[test]
+    fn test_merge_releases() {
+        let existing = vec![
+            release("testnet-v1.40.1", &["a.tgz"]),
+            release("testnet-v1.39.0", &["b.tgz"]),
+        ];
+        let synced = vec![
+            release("testnet-v1.41.0", &["c.tgz"]),
+            release("testnet-v1.40.1", &["a.tgz", "d.tgz"]),
+        ];
+        let merged = merge_releases(existing, synced);
+        let tags: Vec<_> = merged.iter().map(|r| r.tag_name.as_str()).collect();
+        assert_eq!(
+            tags,
+            vec!["testnet-v1.41.0", "testnet-v1.40.1", "testnet-v1.39.0"]
+        );
+        let assets: Vec<_> = merged[1].assets.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(assets, vec!["a.tgz", "d.tgz"]);
+    }
+
+    #[test]
+    fn test_mirror_index() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut index = MirrorIndex::read(temp_dir.path()).unwrap();
+        let entry = IndexEntry {
+            repo: "MystenLabs/sui".to_string(),
+            tag: "testnet-v1.40.1".to_string(),
+            asset: "a.tgz".to_string(),
+            size: 1,
+            sha256: "0".repeat(64),
+        };
+        index.upsert(entry.clone());
+        index.upsert(IndexEntry {
+            sha256: "1".repeat(64),
+            ..entry
+        });
+        index.write(temp_dir.path()).unwrap();
+
+        let index = MirrorIndex::read(temp_dir.path()).unwrap();
+        assert_eq!(index.assets.len(), 1);
+        assert_eq!(
+            index.find(&Repo::Sui, "a.tgz").unwrap().sha256,
+            "1".repeat(64)
+        );
+    }
+}
@@ -12,6 +12,7 @@ use std::path::PathBuf;
 use std::{fs::File, io::BufReader};
 
 use crate::types::{BinaryVersion, InstalledBinaries};
+use shim::install_shim;
 use std::collections::BTreeMap;
 #[cfg(not(windows))]
 use std::fs::set_permissions;
@@ -20,13 +21,26 @@ use std::os::unix::fs::PermissionsExt;
 use tar::Archive;
 use version::extract_version_from_release;
 
+pub mod checksum;
 pub mod download;
 pub mod install;
+pub mod lockfile;
+pub mod manifest;
+pub mod mirror;
+pub mod move_lock;
+pub mod move_toml;
+pub mod profile;
 pub mod release;
+pub mod run;
 pub mod self_;
+pub mod shim;
 pub mod show;
+pub mod signature;
+pub mod sui_config;
 pub mod switch;
+pub mod toolchain;
 pub mod update;
+pub mod verify;
 pub mod version;
 pub mod which;
 pub mod cleanup;
@@ -172,25 +186,19 @@ pub fn update_after_install(
 
                 println!("Setting {} as default", binary);
 
+                if !src.exists() {
+                    return Err(anyhow!(
+                        "Cannot set {binary} as default, binary not found: {}",
+                        src.display()
+                    ));
+                }
+
                 #[cfg(target_os = "windows")]
                 let mut dst = dst.clone();
                 #[cfg(target_os = "windows")]
                 dst.set_extension("exe");
 
-                std::fs::copy(&src, &dst).map_err(|e| {
-                    anyhow!(
-                        "Error copying {binary} to the default folder (src: {}, dst: {}): {e}",
-                        src.display(),
-                        dst.display()
-                    )
-                })?;
-
-                #[cfg(unix)]
-                {
-                    let mut perms = std::fs::metadata(&dst)?.permissions();
-                    perms.set_mode(0o755);
-                    std::fs::set_permissions(&dst, perms)?;
-                }
+                install_shim(&dst)?;
 
                 println!("[{network}] {binary}-{version} set as default");
             }
@@ -353,6 +361,30 @@ pub fn check_if_binaries_exist(
     Ok(path.exists())
 }
 
+/// Returns the path of an installed binary in the binaries folder
+pub fn installed_binary_path(binary: &BinaryVersion) -> PathBuf {
+    let mut path = binaries_dir();
+    path.push(&binary.network_release);
+
+    // cargo install places nightly builds in a `bin` folder
+    if binary.version == "nightly" {
+        path.push("bin");
+    }
+
+    let filename = if binary.debug {
+        format!("{}-debug-{}", binary.binary_name, binary.version)
+    } else {
+        format!("{}-{}", binary.binary_name, binary.version)
+    };
+
+    // the version has dots, so the extension is appended rather than set
+    #[cfg(target_os = "windows")]
+    let filename = format!("{filename}.exe");
+
+    path.push(filename);
+    path
+}
+
 /// Returns a map of installed binaries grouped by network releases
 pub fn installed_binaries_grouped_by_network(
     installed_binaries: Option<InstalledBinaries>,
@@ -0,0 +1,207 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Error};
+use serde::Deserialize;
+
+use crate::handlers::install::install_from_release;
+use crate::handlers::release::{ensure_version_prefix, find_networks_with_version, release_list};
+use crate::options::Options;
+use crate::types::{BinaryVersion, InstalledBinaries, Repo};
+
+pub const MOVE_LOCK_FILE: &str = "Move.lock";
+
+/// Environment variable that enables picking the `sui` binary from the `Move.lock` of the
+/// package in the current directory
+pub const MOVE_LOCK_ENV_VAR: &str = "SUIUP_MOVE_LOCK";
+
+/// Networks to pick a release from when a compiler version is released for several of them
+const NETWORK_PREFERENCE: [&str; 3] = ["mainnet", "testnet", "devnet"];
+
+#[derive(Deserialize, Debug)]
+struct MoveLock {
+    #[serde(rename = "move")]
+    move_: MoveSection,
+}
+
+#[derive(Deserialize, Debug)]
+struct MoveSection {
+    #[serde(rename = "toolchain-version")]
+    toolchain_version: Option<ToolchainVersion>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ToolchainVersion {
+    #[serde(rename = "compiler-version")]
+    compiler_version: Option<String>,
+}
+
+/// Reads `[move.toolchain-version] compiler-version` from a `Move.lock` file, or from the
+/// `Move.lock` in a package directory. Returns the version with a `v` prefix.
+pub fn read_compiler_version(path: &Path) -> Result<Option<String>, Error> {
+    let path = if path.is_dir() {
+        path.join(MOVE_LOCK_FILE)
+    } else {
+        path.to_path_buf()
+    };
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+    let lock: MoveLock =
+        toml::from_str(&content).map_err(|e| anyhow!("Cannot parse {}: {e}", path.display()))?;
+
+    Ok(lock
+        .move_
+        .toolchain_version
+        .and_then(|t| t.compiler_version)
+        .map(|v| ensure_version_prefix(&v)))
+}
+
+/// Walks up from `start` and returns the first `Move.lock` found
+pub fn find_move_lock(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(MOVE_LOCK_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Whether shims should pick the `sui` binary from `Move.lock`
+pub fn move_lock_resolution_enabled() -> bool {
+    std::env::var(MOVE_LOCK_ENV_VAR).is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+}
+
+/// Finds an installed `sui` binary with the given version, a debug build if `debug` is set,
+/// preferring mainnet, then testnet and devnet releases
+pub fn find_installed_sui(
+    installed_binaries: &InstalledBinaries,
+    version: &str,
+    debug: bool,
+) -> Option<BinaryVersion> {
+    NETWORK_PREFERENCE.iter().find_map(|network| {
+        installed_binaries
+            .binaries()
+            .iter()
+            .find(|b| {
+                b.binary_name == "sui"
+                    && b.network_release == *network
+                    && b.version == version
+                    && b.debug == debug
+            })
+            .cloned()
+    })
+}
+
+/// Handles `install --from-move-lock`: installs the `sui` release matching the compiler version
+/// recorded in the package's `Move.lock`.
+pub async fn install_from_move_lock(
+    path: &Path,
+    yes: bool,
+    options: &Options,
+) -> Result<(), Error> {
+    let version = read_compiler_version(path)?.ok_or_else(|| {
+        anyhow!(
+            "No `[move.toolchain-version] compiler-version` found in the Move.lock at {}",
+            path.display()
+        )
+    })?;
+    println!("Move.lock compiler version: {version}");
+
+    let installed_binaries = InstalledBinaries::new()?;
+    let network = match find_installed_sui(&installed_binaries, &version, false) {
+        Some(binary) => binary.network_release,
+        None => {
+            let releases = release_list(&Repo::Sui, options).await?;
+            let networks = find_networks_with_version(&releases, &version);
+            let Some(network) = NETWORK_PREFERENCE
+                .iter()
+                .find(|n| networks.iter().any(|x| x.as_str() == **n))
+            else {
+                bail!("No sui release found for compiler version {version}");
+            };
+            network.to_string()
+        }
+    };
+
+    install_from_release(
+        "sui",
+        &network,
+        Some(version),
+        false,
+        yes,
+        Repo::Sui,
+        options,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_compiler_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let lock = temp_dir.path().join(MOVE_LOCK_FILE);
+        std::fs::write(
+            &lock,
+            r#"# @generated by Move, please check-in and do not edit manually.
+
+[move]
+version = 3
+manifest_digest = "ABC"
+deps_digest = "DEF"
+dependencies = [
+  { id = "Sui", name = "Sui" },
+]
+
+[move.toolchain-version]
+compiler-version = "1.39.3"
+edition = "2024.beta"
+flavor = "sui"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_compiler_version(temp_dir.path()).unwrap(),
+            Some("v1.39.3".to_string())
+        );
+        assert_eq!(
+            read_compiler_version(&lock).unwrap(),
+            Some("v1.39.3".to_string())
+        );
+
+        let nested = temp_dir.path().join("sources");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_move_lock(&nested), Some(lock.clone()));
+
+        std::fs::write(&lock, "[move]\nversion = 3\n").unwrap();
+        assert_eq!(read_compiler_version(&lock).unwrap(), None);
+    }
+
+    #[test]
+    fn test_find_installed_sui() {
+        let binary = |network: &str, debug: bool| BinaryVersion {
+            binary_name: "sui".to_string(),
+            network_release: network.to_string(),
+            version: "v1.39.3".to_string(),
+            debug,
+            path: None,
+            sha256: None,
+            provenance: None,
+        };
+        let installed: InstalledBinaries = serde_json::from_value(serde_json::json!({
+            "binaries": [binary("mainnet", true), binary("testnet", false)]
+        }))
+        .unwrap();
+
+        let found = find_installed_sui(&installed, "v1.39.3", false).unwrap();
+        assert_eq!(found.network_release, "testnet");
+        assert!(!found.debug);
+        let found = find_installed_sui(&installed, "v1.39.3", true).unwrap();
+        assert_eq!(found.network_release, "mainnet");
+        assert!(found.debug);
+        assert!(find_installed_sui(&installed, "v1.40.1", false).is_none());
+    }
+}
@@ -0,0 +1,288 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Error};
+use toml_edit::{value, DocumentMut};
+
+use crate::handlers::shim::resolve_binary;
+use crate::types::BinaryVersion;
+
+pub const MOVE_TOML_FILE: &str = "Move.toml";
+
+/// Dependencies that point to the Sui framework packages
+pub const FRAMEWORK_DEPENDENCIES: &[&str] = &["Sui", "MoveStdlib"];
+
+/// Result of comparing a framework dependency rev with the active sui binary
+#[derive(Debug, Clone, PartialEq)]
+pub enum RevStatus {
+    /// The rev matches the network or the exact release of the active binary
+    Matches,
+    /// The rev points to another network or release
+    Mismatch { expected: String },
+    /// The rev is a commit or branch that cannot be compared with a release
+    Unknown,
+}
+
+/// A git framework dependency found in Move.toml
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameworkDependency {
+    pub name: String,
+    pub rev: String,
+}
+
+fn manifest_path(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        path.join(MOVE_TOML_FILE)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<DocumentMut, Error> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+    content
+        .parse::<DocumentMut>()
+        .map_err(|e| anyhow!("Cannot parse {}: {e}", path.display()))
+}
+
+/// Returns the framework dependencies that are fetched from git with a `rev`
+pub fn framework_dependencies(doc: &DocumentMut) -> Vec<FrameworkDependency> {
+    let Some(deps) = doc.get("dependencies").and_then(|d| d.as_table_like()) else {
+        return vec![];
+    };
+
+    FRAMEWORK_DEPENDENCIES
+        .iter()
+        .filter_map(|name| {
+            let dep = deps.get(name)?.as_table_like()?;
+            dep.get("git")?;
+            let rev = dep.get("rev")?.as_str()?;
+            Some(FrameworkDependency {
+                name: name.to_string(),
+                rev: rev.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Sets the `rev` of all framework git dependencies, keeping the formatting of the manifest
+pub fn set_framework_rev(doc: &mut DocumentMut, rev: &str) {
+    let Some(deps) = doc
+        .get_mut("dependencies")
+        .and_then(|d| d.as_table_like_mut())
+    else {
+        return;
+    };
+
+    for name in FRAMEWORK_DEPENDENCIES {
+        let Some(item) = deps
+            .get_mut(name)
+            .and_then(|d| d.as_table_like_mut())
+            .and_then(|d| d.get_mut("rev"))
+        else {
+            continue;
+        };
+        let decor = item.as_value().map(|v| v.decor().clone());
+        *item = value(rev);
+        if let (Some(decor), Some(v)) = (decor, item.as_value_mut()) {
+            *v.decor_mut() = decor;
+        }
+    }
+}
+
+/// The rev that tracks the framework of a network, e.g. `framework/testnet`
+pub fn network_rev(network: &str) -> String {
+    format!("framework/{network}")
+}
+
+/// The release tag of a binary, e.g. `testnet-v1.39.3`
+pub fn release_rev(network: &str, version: &str) -> String {
+    format!("{network}-{version}")
+}
+
+/// Compares a framework rev with the network/version of the active sui binary
+pub fn check_rev(rev: &str, network: &str, version: &str) -> RevStatus {
+    if rev == network_rev(network) || rev == release_rev(network, version) {
+        return RevStatus::Matches;
+    }
+
+    let is_network_rev = rev.starts_with("framework/");
+    let is_release_rev = ["testnet-", "devnet-", "mainnet-"]
+        .iter()
+        .any(|prefix| rev.starts_with(prefix));
+
+    if is_network_rev {
+        RevStatus::Mismatch {
+            expected: network_rev(network),
+        }
+    } else if is_release_rev {
+        RevStatus::Mismatch {
+            expected: release_rev(network, version),
+        }
+    } else {
+        RevStatus::Unknown
+    }
+}
+
+/// Returns the active sui binary, which must come from a network release
+fn active_sui() -> Result<BinaryVersion, Error> {
+    let resolved = resolve_binary("sui")?;
+    let binary = resolved.binary;
+    if !["testnet", "devnet", "mainnet"].contains(&binary.network_release.as_str()) {
+        bail!(
+            "The active sui binary is built from the `{}` branch and cannot be matched with a framework release",
+            binary.network_release
+        );
+    }
+    println!(
+        "Active sui: {}-{} (from {})",
+        binary.network_release, binary.version, resolved.from
+    );
+    Ok(binary)
+}
+
+/// Handles the `move check` command
+pub fn handle_move_check(path: &Path) -> Result<(), Error> {
+    let path = manifest_path(path);
+    let doc = read_manifest(&path)?;
+    let deps = framework_dependencies(&doc);
+    if deps.is_empty() {
+        println!(
+            "No git framework dependencies with a `rev` found in {}",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let sui = active_sui()?;
+    let mut mismatches = 0;
+    for dep in deps {
+        match check_rev(&dep.rev, &sui.network_release, &sui.version) {
+            RevStatus::Matches => println!("{}: rev `{}` matches", dep.name, dep.rev),
+            RevStatus::Mismatch { expected } => {
+                mismatches += 1;
+                println!(
+                    "{}: rev `{}` does not match the active sui, expected `{expected}`",
+                    dep.name, dep.rev
+                );
+            }
+            RevStatus::Unknown => println!(
+                "{}: rev `{}` is not a framework release and cannot be checked",
+                dep.name, dep.rev
+            ),
+        }
+    }
+
+    if mismatches > 0 {
+        bail!("Framework revs in {} do not match the active sui binary. Run `suiup move pin` to fix them", path.display());
+    }
+    Ok(())
+}
+
+/// Handles the `move pin` command: rewrites the framework revs to match the active sui binary,
+/// preserving the rest of the manifest
+pub fn handle_move_pin(path: &Path, exact: bool) -> Result<(), Error> {
+    let path = manifest_path(path);
+    let mut doc = read_manifest(&path)?;
+    let deps = framework_dependencies(&doc);
+    if deps.is_empty() {
+        bail!(
+            "No git framework dependencies with a `rev` found in {}",
+            path.display()
+        );
+    }
+
+    let sui = active_sui()?;
+    let rev = if exact {
+        release_rev(&sui.network_release, &sui.version)
+    } else {
+        network_rev(&sui.network_release)
+    };
+
+    set_framework_rev(&mut doc, &rev);
+    for dep in &deps {
+        println!("{}: rev `{}` -> `{rev}`", dep.name, dep.rev);
+    }
+
+    std::fs::write(&path, doc.to_string())
+        .map_err(|e| anyhow!("Cannot write {}: {e}", path.display()))?;
+    println!("Updated {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[package]
+name = "example"
+edition = "2024.beta" # edition comment
+
+[dependencies]
+Sui = { git = "https://github.com/MystenLabs/sui.git", subdir = "crates/sui-framework/packages/sui-framework", rev = "framework/devnet" }
+
+[dependencies.MoveStdlib]
+git = "https://github.com/MystenLabs/sui.git"
+subdir = "crates/sui-framework/packages/move-stdlib"
+rev = "testnet-v1.39.3"
+
+[addresses]
+example = "0x0"
+"#;
+
+    #[test]
+    fn test_check_rev() {
+        assert_eq!(
+            check_rev("framework/testnet", "testnet", "v1.39.3"),
+            RevStatus::Matches
+        );
+        assert_eq!(
+            check_rev("testnet-v1.39.3", "testnet", "v1.39.3"),
+            RevStatus::Matches
+        );
+        assert_eq!(
+            check_rev("framework/devnet", "testnet", "v1.39.3"),
+            RevStatus::Mismatch {
+                expected: "framework/testnet".to_string()
+            }
+        );
+        assert_eq!(
+            check_rev("testnet-v1.38.0", "testnet", "v1.39.3"),
+            RevStatus::Mismatch {
+                expected: "testnet-v1.39.3".to_string()
+            }
+        );
+        assert_eq!(
+            check_rev("a1b2c3d4", "testnet", "v1.39.3"),
+            RevStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_framework_dependencies_and_pin() {
+        let mut doc = MANIFEST.parse::<DocumentMut>().unwrap();
+        let deps = framework_dependencies(&doc);
+        assert_eq!(
+            deps,
+            vec![
+                FrameworkDependency {
+                    name: "Sui".to_string(),
+                    rev: "framework/devnet".to_string()
+                },
+                FrameworkDependency {
+                    name: "MoveStdlib".to_string(),
+                    rev: "testnet-v1.39.3".to_string()
+                },
+            ]
+        );
+
+        set_framework_rev(&mut doc, "framework/testnet");
+        let updated = doc.to_string();
+        assert!(updated.contains("edition = \"2024.beta\" # edition comment"));
+        assert!(updated.contains("[addresses]"));
+        assert_eq!(updated.matches("rev = \"framework/testnet\"").count(), 2);
+    }
+}
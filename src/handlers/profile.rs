@@ -0,0 +1,328 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Error};
+use comfy_table::Table;
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+use crate::commands::TABLE_FORMAT;
+use crate::handlers::available_components;
+use crate::handlers::sui_config::{default_rpc_url, set_active_env, CLIENT_CONFIG_FILE};
+use crate::handlers::switch::handle_switch;
+use crate::paths::{get_config_file, profiles_dir};
+
+const PROFILE_FILE: &str = "profile.json";
+const ACTIVE_PROFILE_FILE: &str = "active_profile";
+
+/// A suiup-managed sui config profile. Each profile has its own sui config dir (client.yaml,
+/// keystore and aliases) and is coupled with the network of the binaries to use with it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Profile {
+    /// Network of the client env and of the default binaries for this profile
+    pub network: String,
+    /// Binaries switched to the profile's network when the profile is used
+    #[serde(default)]
+    pub binaries: Vec<String>,
+}
+
+fn validate_name(name: &str) -> Result<(), Error> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        bail!("Invalid profile name `{name}`. Use letters, digits, '-' and '_'");
+    }
+    Ok(())
+}
+
+/// Returns the folder of a profile
+pub fn profile_dir(name: &str) -> PathBuf {
+    profiles_dir().join(name)
+}
+
+/// Returns the sui config dir of a profile, to be used as `SUI_CONFIG_DIR`
+pub fn profile_config_dir(name: &str) -> PathBuf {
+    profile_dir(name).join("sui_config")
+}
+
+pub fn load_profile(name: &str) -> Result<Profile, Error> {
+    validate_name(name)?;
+    let path = profile_dir(name).join(PROFILE_FILE);
+    if !path.exists() {
+        bail!("Profile `{name}` does not exist. Use `suiup profile list` to see the profiles");
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+    serde_json::from_str(&content)
+        .map_err(|e| anyhow!("Cannot deserialize profile file {}: {e}", path.display()))
+}
+
+fn save_profile(name: &str, profile: &Profile) -> Result<(), Error> {
+    let path = profile_dir(name).join(PROFILE_FILE);
+    let content = serde_json::to_string_pretty(profile)
+        .map_err(|e| anyhow!("Cannot serialize profile: {e}"))?;
+    std::fs::write(&path, content).map_err(|e| anyhow!("Cannot write {}: {e}", path.display()))
+}
+
+/// Returns the name of the active profile, if any
+pub fn active_profile() -> Result<Option<String>, Error> {
+    let path = get_config_file(ACTIVE_PROFILE_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let name = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?
+        .trim()
+        .to_string();
+    if name.is_empty() {
+        return Ok(None);
+    }
+    // the name is joined into paths, never trust what is on disk
+    validate_name(&name).map_err(|e| anyhow!("{e} in {}", path.display()))?;
+    Ok(Some(name))
+}
+
+/// Returns the sui config dir of the active profile, if any
+pub fn active_profile_config_dir() -> Result<Option<PathBuf>, Error> {
+    Ok(active_profile()?.map(|name| profile_config_dir(&name)))
+}
+
+/// Returns the names of all profiles
+pub fn list_profiles() -> Result<Vec<String>, Error> {
+    let dir = profiles_dir();
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut names = vec![];
+    for entry in std::fs::read_dir(&dir)? {
+        let entry = entry?;
+        if entry.path().join(PROFILE_FILE).is_file() {
+            names.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Creates the client.yaml, keystore and aliases files for a new sui config dir
+fn create_sui_config(dir: &std::path::Path, network: &str) -> Result<(), Error> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| anyhow!("Cannot create folder {}: {e}", dir.display()))?;
+
+    let keystore = dir.join("sui.keystore");
+    std::fs::write(&keystore, "[]")?;
+    std::fs::write(dir.join("sui.aliases"), "[]")?;
+
+    let mut keystore_entry = Mapping::new();
+    keystore_entry.insert("File".into(), keystore.to_string_lossy().to_string().into());
+    let mut config = Mapping::new();
+    config.insert("keystore".into(), Value::Mapping(keystore_entry));
+    config.insert("envs".into(), Value::Sequence(vec![]));
+    config.insert("active_env".into(), Value::Null);
+    config.insert("active_address".into(), Value::Null);
+    let mut config = Value::Mapping(config);
+    set_active_env(&mut config, network)?;
+
+    let content = serde_yaml::to_string(&config)
+        .map_err(|e| anyhow!("Cannot serialize the client config: {e}"))?;
+    std::fs::write(dir.join(CLIENT_CONFIG_FILE), content)?;
+    Ok(())
+}
+
+/// Handles the `profile create` command
+pub fn handle_profile_create(name: &str, network: &str, binaries: &[String]) -> Result<(), Error> {
+    validate_name(name)?;
+    if default_rpc_url(network).is_none() {
+        bail!("Invalid network `{network}`. Use one of: mainnet, testnet, devnet, localnet");
+    }
+    for binary in binaries {
+        if !available_components().contains(&binary.as_str()) {
+            bail!("Unknown binary `{binary}`. Use `suiup list` to find available binaries.");
+        }
+    }
+
+    let dir = profile_dir(name);
+    if dir.exists() {
+        bail!("Profile `{name}` already exists");
+    }
+
+    create_sui_config(&profile_config_dir(name), network)?;
+    save_profile(
+        name,
+        &Profile {
+            network: network.to_string(),
+            binaries: binaries.to_vec(),
+        },
+    )?;
+
+    println!(
+        "Created profile `{name}` for {network} at {}",
+        profile_config_dir(name).display()
+    );
+    println!("Use `suiup profile use {name}` to activate it");
+    Ok(())
+}
+
+/// Handles the `profile use` command: activates the profile and switches its binaries to the
+/// profile's network
+pub fn handle_profile_use(name: &str) -> Result<(), Error> {
+    let profile = load_profile(name)?;
+
+    for binary in &profile.binaries {
+        let spec = format!("{binary}@{}", profile.network);
+        if let Err(e) = handle_switch(&spec, false) {
+            println!(
+                "WARNING: cannot switch {binary} to {}: {e}",
+                profile.network
+            );
+        }
+    }
+
+    std::fs::write(get_config_file(ACTIVE_PROFILE_FILE), name)?;
+    println!(
+        "Profile `{name}` is active. Shims and `suiup run` use SUI_CONFIG_DIR={}",
+        profile_config_dir(name).display()
+    );
+    Ok(())
+}
+
+/// Handles the `profile deactivate` command
+pub fn handle_profile_deactivate() -> Result<(), Error> {
+    let path = get_config_file(ACTIVE_PROFILE_FILE);
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+    println!("No profile is active. The default sui config dir is used");
+    Ok(())
+}
+
+/// Handles the `profile list` command
+pub fn handle_profile_list() -> Result<(), Error> {
+    let active = active_profile()?;
+    let mut table = Table::new();
+    table
+        .load_preset(TABLE_FORMAT)
+        .set_header(vec!["Profile", "Network", "Binaries", "Active"]);
+    for name in list_profiles()? {
+        let profile = load_profile(&name)?;
+        let is_active = active.as_deref() == Some(name.as_str());
+        table.add_row(vec![
+            name,
+            profile.network,
+            profile.binaries.join(", "),
+            if is_active { "Yes" } else { "No" }.to_string(),
+        ]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// Handles the `profile remove` command. This deletes the profile's keystore.
+pub fn handle_profile_remove(name: &str, yes: bool) -> Result<(), Error> {
+    load_profile(name)?;
+
+    if !yes {
+        print!("This deletes the keystore of profile `{name}`. Continue? [y/N] ");
+        std::io::stdout().flush()?;
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+            println!("Keeping profile `{name}`");
+            return Ok(());
+        }
+    }
+
+    if active_profile()?.as_deref() == Some(name) {
+        handle_profile_deactivate()?;
+    }
+    std::fs::remove_dir_all(profile_dir(name))?;
+    println!("Removed profile `{name}`");
+    Ok(())
+}
+
+/// Handles the `env` command: prints shell commands that point `SUI_CONFIG_DIR` to a profile
+pub fn handle_env(profile: Option<String>, shell: &str) -> Result<(), Error> {
+    let name = match profile {
+        Some(name) => name,
+        None => active_profile()?.ok_or_else(|| {
+            anyhow!("No active profile. Pass a profile name or run `suiup profile use`")
+        })?,
+    };
+    load_profile(&name)?;
+    let dir = profile_config_dir(&name);
+    println!("{}", env_command(shell, &dir.to_string_lossy()));
+    Ok(())
+}
+
+/// Returns the shell command setting `SUI_CONFIG_DIR` to `dir`. The value is a single-quoted
+/// literal, so that nothing in it is expanded by `eval`.
+fn env_command(shell: &str, dir: &str) -> String {
+    match shell {
+        "fish" => {
+            let dir = dir.replace('\\', "\\\\").replace('\'', "\\'");
+            format!("set -gx SUI_CONFIG_DIR '{dir}'")
+        }
+        "powershell" => format!("$env:SUI_CONFIG_DIR = '{}'", dir.replace('\'', "''")),
+        _ => format!("export SUI_CONFIG_DIR='{}'", dir.replace('\'', "'\\''")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("mainnet-ops").is_ok());
+        assert!(validate_name("test_1").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../keys").is_err());
+        assert!(validate_name("a b").is_err());
+    }
+
+    #[test]
+    fn test_env_command() {
+        let dir = r#"/home/o'neil/"$x"/`id`"#;
+        assert_eq!(
+            env_command("bash", dir),
+            r#"export SUI_CONFIG_DIR='/home/o'\''neil/"$x"/`id`'"#
+        );
+        assert_eq!(
+            env_command("fish", dir),
+            r#"set -gx SUI_CONFIG_DIR '/home/o\'neil/"$x"/`id`'"#
+        );
+        assert_eq!(
+            env_command("powershell", r"C:\Users\o'neil"),
+            r"$env:SUI_CONFIG_DIR = 'C:\Users\o''neil'"
+        );
+    }
+
+    #[test]
+    fn test_create_sui_config() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("sui_config");
+        create_sui_config(&dir, "testnet").unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.join("sui.keystore")).unwrap(),
+            "[]"
+        );
+        let config: Value =
+            serde_yaml::from_str(&std::fs::read_to_string(dir.join(CLIENT_CONFIG_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(config["active_env"].as_str(), Some("testnet"));
+        assert_eq!(
+            config["envs"][0]["rpc"].as_str(),
+            Some("https://fullnode.testnet.sui.io:443")
+        );
+        assert_eq!(
+            config["keystore"]["File"].as_str(),
+            Some(dir.join("sui.keystore").to_string_lossy().as_ref())
+        );
+    }
+}
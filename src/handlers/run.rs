@@ -0,0 +1,44 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ffi::OsString;
+
+use anyhow::{anyhow, bail, Error};
+
+use crate::handlers::install::add_from_pin;
+use crate::handlers::installed_binary_path;
+use crate::handlers::shim::{exec_binary, resolve_binary};
+use crate::handlers::toolchain::{parse_pin, resolve_pin};
+use crate::options::Options;
+use crate::types::InstalledBinaries;
+
+/// Handles the `run` command: runs an installed binary without changing the default version.
+///
+/// Without a version (e.g. `sui`), the binary is resolved the same way as the shims do.
+pub async fn handle_run(
+    spec: &str,
+    args: Vec<OsString>,
+    install: bool,
+    options: &Options,
+) -> Result<(), Error> {
+    let Some((binary, version_spec)) = spec.split_once('@') else {
+        let resolved = resolve_binary(spec)?;
+        return exec_binary(&resolved.path, args);
+    };
+
+    let pin = parse_pin(binary, version_spec)?;
+    let installed_binaries = InstalledBinaries::new()?;
+    let binary_version = match resolve_pin(&installed_binaries, &pin, false) {
+        Some(binary_version) => binary_version,
+        None if install => {
+            add_from_pin(&pin, false, options).await?;
+            resolve_pin(&InstalledBinaries::new()?, &pin, false)
+                .ok_or_else(|| anyhow!("Could not find {spec} after installing it"))?
+        }
+        None => bail!(
+            "{spec} is not installed. Run `suiup install {spec}` or pass `--install` to install it first"
+        ),
+    };
+
+    exec_binary(&installed_binary_path(&binary_version), args)
+}
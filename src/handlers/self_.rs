@@ -3,26 +3,28 @@
 
 use super::download::detect_os_arch;
 
-use crate::handlers::download::download_file;
+use crate::handlers::download::download_verified_asset;
+use crate::handlers::shim::refresh_shims;
+use crate::options::Options;
+use crate::source::release_source;
+use crate::types::Repo;
 use anyhow::{anyhow, Result};
 use std::{fmt::Display, process::Command};
 use tokio::task;
 
 use flate2::read::GzDecoder;
-use serde::Deserialize;
 use std::fs::File;
 use tar::Archive;
 
-#[derive(Debug, Deserialize)]
-struct GitHubRelease {
-    tag_name: String,
-}
-
-pub fn check_for_updates() {
-    task::spawn(check_for_updates_impl());
+pub fn check_for_updates(options: Options) {
+    let options = Options {
+        best_effort: true,
+        ..options
+    };
+    task::spawn(async move { check_for_updates_impl(&options).await });
 }
 
-async fn check_for_updates_impl() -> Option<()> {
+async fn check_for_updates_impl(options: &Options) -> Option<()> {
     let current_exe = std::env::current_exe().ok()?;
     let output = std::process::Command::new(current_exe)
         .arg("--version")
@@ -33,7 +35,7 @@ async fn check_for_updates_impl() -> Option<()> {
     let version = version_output.split_whitespace().nth(1)?;
     let current_version = Ver::from_str(version).ok()?;
 
-    let latest_version = get_latest_version().await.ok()?;
+    let latest_version = get_latest_version(options).await.ok()?;
 
     if current_version < latest_version {
         eprintln!(
@@ -45,19 +47,8 @@ async fn check_for_updates_impl() -> Option<()> {
     Some(())
 }
 
-async fn get_latest_version() -> Result<Ver> {
-    let client = reqwest::Client::new();
-    let response = client
-        .get("https://api.github.com/repos/MystenLabs/suiup/releases/latest")
-        .header("User-Agent", "suiup")
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        return Err(anyhow!("Failed to fetch latest version from GitHub"));
-    }
-
-    let release: GitHubRelease = response.json().await?;
+async fn get_latest_version(options: &Options) -> Result<Ver> {
+    let release = release_source(options).latest_release(&Repo::Suiup).await?;
     Ver::from_str(&release.tag_name)
 }
 
@@ -96,7 +87,7 @@ impl Display for Ver {
     }
 }
 
-pub async fn handle_update() -> Result<()> {
+pub async fn handle_update(options: &Options) -> Result<()> {
     // find the current binary version
     let current_exe = std::env::current_exe()?;
     let current_version = Command::new(&current_exe).arg("--version").output()?.stdout;
@@ -118,21 +109,10 @@ pub async fn handle_update() -> Result<()> {
 
     let current_version = Ver::from_str(split[1])?;
 
-    // find the latest version on github in releases
-    let repo = "https://api.github.com/repos/MystenLabs/suiup/releases/latest";
-    let client = reqwest::Client::new();
-    let response = client
-        .get(repo)
-        .header("User-Agent", "suiup")
-        .send()
-        .await?
-        .json::<serde_json::Value>()
-        .await?;
-    let tag = response["tag_name"]
-        .as_str()
-        .ok_or_else(|| anyhow::anyhow!("Failed to parse latest version from GitHub response"))?;
-
-    let latest_version = Ver::from_str(tag)?;
+    // find the latest version in the releases
+    let source = release_source(options);
+    let release = source.latest_release(&Repo::Suiup).await?;
+    let latest_version = Ver::from_str(&release.tag_name)?;
 
     if current_version == latest_version {
         println!("suiup is already up to date");
@@ -141,16 +121,25 @@ pub async fn handle_update() -> Result<()> {
         println!("Updating to latest version: {}", latest_version);
     }
 
-    // download the latest version from github
-    // https://github.com/MystenLabs/suiup/releases/download/v0.0.1/suiup-Linux-musl-x86_64.tar.gz
-
+    // download the latest version, e.g. suiup-Linux-musl-x86_64.tar.gz
     let archive_name = find_archive_name()?;
-    let url =
-        format!("https://github.com/MystenLabs/suiup/releases/download/{tag}/{archive_name}",);
+    let asset = release
+        .assets
+        .iter()
+        .find(|a| a.name == archive_name)
+        .ok_or_else(|| anyhow!("No {archive_name} in suiup release {}", release.tag_name))?;
 
     let temp_dir = tempfile::tempdir()?;
     let archive_path = temp_dir.path().join(&archive_name);
-    download_file(&url, &temp_dir.path().join(archive_name), "suiup", None).await?;
+    download_verified_asset(
+        source.as_ref(),
+        &Repo::Suiup,
+        &release.assets,
+        asset,
+        &archive_path,
+        options,
+    )
+    .await?;
 
     // extract the archive
     let file = File::open(archive_path.as_path())
@@ -170,6 +159,9 @@ pub async fn handle_update() -> Result<()> {
     let binary_path = temp_dir.path().join(binary);
     std::fs::copy(binary_path, current_exe)?;
 
+    // shims that are copies of suiup would still run the old version
+    refresh_shims()?;
+
     println!("suiup updated to version {}", latest_version);
     // cleanup
     temp_dir.close()?;
@@ -0,0 +1,307 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Error};
+use tracing::debug;
+
+use crate::handlers::move_lock::{
+    find_installed_sui, find_move_lock, move_lock_resolution_enabled, read_compiler_version,
+};
+use crate::handlers::profile::active_profile_config_dir;
+use crate::handlers::sui_config::SUI_CONFIG_DIR_ENV;
+use crate::handlers::switch::get_binary_destination_path;
+use crate::handlers::toolchain::{load_active_toolchain, parse_pin, resolve_pin};
+use crate::handlers::{available_components, installed_binary_path};
+use crate::paths::default_file_path;
+use crate::types::{BinaryVersion, InstalledBinaries, Version};
+
+/// Where the binary a shim dispatches to was selected from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolvedFrom {
+    /// A `SUIUP_<BINARY>_VERSION` environment variable
+    Env(String),
+    /// A toolchain file found in the current directory or its parents
+    Toolchain(PathBuf),
+    /// The compiler version recorded in a package's `Move.lock`
+    MoveLock(PathBuf),
+    /// The global default from `default_version.json`
+    Default,
+}
+
+impl Display for ResolvedFrom {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolvedFrom::Env(var) => write!(f, "environment variable {var}"),
+            ResolvedFrom::Toolchain(path) => write!(f, "toolchain file {}", path.display()),
+            ResolvedFrom::MoveLock(path) => write!(f, "compiler version in {}", path.display()),
+            ResolvedFrom::Default => write!(f, "default version"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedBinary {
+    pub binary: BinaryVersion,
+    pub path: PathBuf,
+    pub from: ResolvedFrom,
+}
+
+/// Returns the shim name if suiup was invoked through one of its shims (e.g. as `sui`).
+pub fn invoked_shim_name() -> Option<String> {
+    let arg0 = std::env::args_os().next()?;
+    let name = Path::new(&arg0).file_stem()?.to_str()?.to_string();
+    is_shim_name(&name).then_some(name)
+}
+
+/// Whether suiup dispatches to a managed binary when invoked under this name
+pub fn is_shim_name(name: &str) -> bool {
+    let base = name.strip_suffix("-debug").unwrap_or(name);
+    available_components().contains(&base)
+}
+
+/// Name of the environment variable that overrides the version of a binary, e.g.
+/// `SUIUP_SUI_VERSION=testnet-1.39.3` or `SUIUP_SITE_BUILDER_VERSION=mainnet`.
+pub fn version_env_var(binary: &str) -> String {
+    format!("SUIUP_{}_VERSION", binary.to_uppercase().replace('-', "_"))
+}
+
+/// Resolves which installed binary a shim should run. The env var takes precedence over a
+/// project toolchain file, then the `Move.lock` compiler version (for `sui`, when enabled with
+/// `SUIUP_MOVE_LOCK`), then the global default.
+pub fn resolve_binary(name: &str) -> Result<ResolvedBinary, Error> {
+    let (base, debug) = match name.strip_suffix("-debug") {
+        Some(base) => (base, true),
+        None => (name, false),
+    };
+
+    let env_var = version_env_var(base);
+    if let Ok(spec) = std::env::var(&env_var) {
+        let pin = parse_pin(base, &spec)?;
+        let installed_binaries = InstalledBinaries::new()?;
+        let binary = resolve_pin(&installed_binaries, &pin, debug).ok_or_else(|| {
+            anyhow!("{base}@{spec} (from {env_var}) is not installed. Run `suiup install {base}@{spec}`")
+        })?;
+        return Ok(resolved(binary, debug, ResolvedFrom::Env(env_var)));
+    }
+
+    if let Some(active) = load_active_toolchain()? {
+        if let Some(pin) = active.file.pin(base)? {
+            let installed_binaries = InstalledBinaries::new()?;
+            let spec = &active.file.toolchain[base];
+            let binary = resolve_pin(&installed_binaries, &pin, debug).ok_or_else(|| {
+                anyhow!(
+                    "{base}@{spec} (pinned in {}) is not installed. Run `suiup install {base}@{spec}`",
+                    active.path.display()
+                )
+            })?;
+            return Ok(resolved(
+                binary,
+                debug,
+                ResolvedFrom::Toolchain(active.path),
+            ));
+        }
+    }
+
+    if base == "sui" && move_lock_resolution_enabled() {
+        if let Some(lock) = find_move_lock(&std::env::current_dir()?) {
+            if let Some(version) = read_compiler_version(&lock)? {
+                let installed_binaries = InstalledBinaries::new()?;
+                let binary = find_installed_sui(&installed_binaries, &version, debug).ok_or_else(|| {
+                    anyhow!(
+                        "sui {version} (compiler version in {}) is not installed. Run `suiup install --from-move-lock`",
+                        lock.display()
+                    )
+                })?;
+                return Ok(resolved(binary, debug, ResolvedFrom::MoveLock(lock)));
+            }
+        }
+    }
+
+    let binary = default_binary(name)?.ok_or_else(|| {
+        anyhow!("No default version set for {name}. Use `suiup install {base}` or `suiup default set` to set one")
+    })?;
+    Ok(resolved(binary, debug, ResolvedFrom::Default))
+}
+
+fn resolved(mut binary: BinaryVersion, debug: bool, from: ResolvedFrom) -> ResolvedBinary {
+    binary.debug |= debug;
+    ResolvedBinary {
+        path: installed_binary_path(&binary),
+        binary,
+        from,
+    }
+}
+
+/// Reads the default version file: binary name to network, version and debug flag
+fn read_default_file() -> Result<BTreeMap<String, (String, Version, bool)>, Error> {
+    let default = std::fs::read_to_string(default_file_path()?)?;
+    serde_json::from_str(&default)
+        .map_err(|_| anyhow!("Cannot decode default binary file to JSON. Is the file corrupted?"))
+}
+
+/// Reads the global default for a binary from the default version file
+fn default_binary(name: &str) -> Result<Option<BinaryVersion>, Error> {
+    let default = read_default_file()?;
+
+    let base = name.strip_suffix("-debug");
+    let entry = default.get(name).or_else(|| {
+        base.and_then(|b| default.get(b))
+            .filter(|(_, _, debug)| *debug)
+    });
+
+    Ok(entry.map(|(network, version, debug)| BinaryVersion {
+        binary_name: base.unwrap_or(name).to_string(),
+        network_release: network.clone(),
+        version: version.clone(),
+        debug: *debug,
+        path: None,
+        sha256: None,
+        provenance: None,
+    }))
+}
+
+/// Runs the binary resolved for `name` with the given arguments. On Unix the current process is
+/// replaced, so stdio and the exit code are exactly those of the binary.
+pub fn run_shim(name: &str, args: impl IntoIterator<Item = OsString>) -> Result<(), Error> {
+    let resolved = resolve_binary(name)?;
+    debug!(
+        "Running {} (selected by {})",
+        resolved.path.display(),
+        resolved.from
+    );
+    exec_binary(&resolved.path, args)
+}
+
+/// Executes a binary with the given arguments, propagating its exit code
+pub fn exec_binary(path: &Path, args: impl IntoIterator<Item = OsString>) -> Result<(), Error> {
+    if !path.exists() {
+        bail!(
+            "Binary {} does not exist. Use `suiup show` to see installed binaries",
+            path.display()
+        );
+    }
+
+    let mut cmd = Command::new(path);
+    cmd.args(args);
+
+    // point the binary to the active profile's sui config, unless the caller chose one
+    if std::env::var_os(SUI_CONFIG_DIR_ENV).is_none() {
+        if let Some(dir) = active_profile_config_dir()? {
+            cmd.env(SUI_CONFIG_DIR_ENV, dir);
+        }
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let err = cmd.exec();
+        Err(anyhow!("Cannot execute {}: {err}", path.display()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let status = cmd
+            .status()
+            .map_err(|e| anyhow!("Cannot execute {}: {e}", path.display()))?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// Places a shim at `dst` that dispatches to the right binary at exec time. The shim is a hard
+/// link to the running suiup executable, or a copy if linking is not possible.
+pub fn install_shim(dst: &Path) -> Result<(), Error> {
+    let suiup =
+        std::env::current_exe().map_err(|e| anyhow!("Cannot find the suiup executable: {e}"))?;
+
+    if dst.exists() || dst.is_symlink() {
+        std::fs::remove_file(dst).map_err(|e| anyhow!("Cannot remove {}: {e}", dst.display()))?;
+    }
+
+    if let Err(e) = std::fs::hard_link(&suiup, dst) {
+        debug!(
+            "Cannot hard link shim {}: {e}. Copying instead",
+            dst.display()
+        );
+        std::fs::copy(&suiup, dst).map_err(|e| {
+            anyhow!(
+                "Error creating shim (src: {}, dst: {}): {e}",
+                suiup.display(),
+                dst.display()
+            )
+        })?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = std::fs::metadata(dst)?.permissions();
+            perms.set_mode(0o755);
+            std::fs::set_permissions(dst, perms)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the shim serving a default binary. A debug build set as default at install time is
+/// served by the `sui` shim, one set with `switch` by `sui-debug`.
+pub fn default_shim_path(binary: &BinaryVersion) -> PathBuf {
+    let shim = get_binary_destination_path(binary);
+    if binary.debug && !shim.exists() {
+        let release_shim = get_binary_destination_path(&BinaryVersion {
+            debug: false,
+            ..binary.clone()
+        });
+        if release_shim.exists() {
+            return release_shim;
+        }
+    }
+    shim
+}
+
+/// Reinstalls the shims of all default binaries from the running suiup executable. Shims that are
+/// copies rather than hard links keep running the old suiup otherwise, e.g. after a self update.
+pub fn refresh_shims() -> Result<(), Error> {
+    for (name, (network, version, debug)) in read_default_file()? {
+        let binary = BinaryVersion {
+            binary_name: name,
+            network_release: network,
+            version,
+            debug,
+            path: None,
+            sha256: None,
+            provenance: None,
+        };
+        install_shim(&default_shim_path(&binary))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_shim_name() {
+        assert!(is_shim_name("sui"));
+        assert!(is_shim_name("sui-debug"));
+        assert!(is_shim_name("site-builder"));
+        assert!(is_shim_name("mvr"));
+        assert!(!is_shim_name("suiup"));
+        assert!(!is_shim_name("cargo"));
+    }
+
+    #[test]
+    fn test_version_env_var() {
+        assert_eq!(version_env_var("sui"), "SUIUP_SUI_VERSION");
+        assert_eq!(
+            version_env_var("site-builder"),
+            "SUIUP_SITE_BUILDER_VERSION"
+        );
+    }
+}
@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    handlers::installed_binaries_grouped_by_network,
+    handlers::{
+        installed_binaries_grouped_by_network,
+        profile::{active_profile, profile_config_dir},
+        toolchain::{load_active_toolchain, print_active_toolchain},
+    },
     paths::default_file_path,
     types::{Binaries, Version},
 };
@@ -21,6 +25,18 @@ pub fn handle_show(default_only: bool) -> Result<(), Error> {
     println!("\x1b[1mDefault binaries:\x1b[0m");
     print_table(&default_binaries.binaries);
 
+    // Toolchain pins from a suiup.toml in the current directory or its parents
+    if let Some(active) = load_active_toolchain()? {
+        print_active_toolchain(&active)?;
+    }
+
+    if let Some(profile) = active_profile()? {
+        println!(
+            "\x1b[1mActive profile:\x1b[0m {profile} ({})",
+            profile_config_dir(&profile).display()
+        );
+    }
+
     // Only show installed binaries if --default flag is not set
     if !default_only {
         // Installed binaries table
@@ -0,0 +1,319 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of minisign signatures published next to release assets, against public keys
+//! pinned per repository in `trusted_keys.json`.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Error};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use blake2::{Blake2b512, Digest};
+use ed25519_dalek::{Signature as Ed25519Signature, VerifyingKey};
+
+use crate::handlers::download::fetch_sidecar;
+use crate::options::Options;
+use crate::paths::get_config_file;
+use crate::source::ReleaseSource;
+use crate::types::{Asset, Repo};
+
+pub const TRUSTED_KEYS_FILE: &str = "trusted_keys.json";
+
+/// Extension of the signature asset published next to a release asset
+pub const SIGNATURE_EXTENSION: &str = "minisig";
+
+/// A minisign Ed25519 public key
+#[derive(Debug, Clone)]
+pub struct PublicKey {
+    key_id: [u8; 8],
+    key: VerifyingKey,
+}
+
+/// A minisign signature file
+#[derive(Debug, Clone)]
+pub struct Signature {
+    /// Whether the file was hashed with BLAKE2b-512 before signing (`ED`) or signed as is (`Ed`)
+    prehashed: bool,
+    key_id: [u8; 8],
+    signature: [u8; 64],
+    trusted_comment: String,
+    global_signature: [u8; 64],
+}
+
+fn key_id_hex(key_id: &[u8; 8]) -> String {
+    // minisign shows key ids as little endian numbers
+    key_id.iter().rev().map(|b| format!("{b:02X}")).collect()
+}
+
+impl PublicKey {
+    /// Parses a public key, either the base64 line or the content of a minisign `.pub` file
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let line = content
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty() && !l.starts_with("untrusted comment:"))
+            .ok_or_else(|| anyhow!("Empty public key"))?;
+        let bytes = STANDARD
+            .decode(line)
+            .map_err(|e| anyhow!("Invalid public key encoding: {e}"))?;
+        if bytes.len() != 42 || &bytes[..2] != b"Ed" {
+            bail!("Invalid public key: expected a minisign Ed25519 key");
+        }
+        let key = VerifyingKey::from_bytes(bytes[10..].try_into()?)
+            .map_err(|e| anyhow!("Invalid public key: {e}"))?;
+        Ok(Self {
+            key_id: bytes[2..10].try_into()?,
+            key,
+        })
+    }
+
+    pub fn key_id(&self) -> String {
+        key_id_hex(&self.key_id)
+    }
+}
+
+impl Signature {
+    /// Parses the content of a minisign `.minisig` file
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let lines: Vec<&str> = content.lines().map(str::trim_end).collect();
+        let [_, signature, trusted_comment, global_signature, ..] = lines.as_slice() else {
+            bail!("Invalid signature file: expected 4 lines");
+        };
+
+        let signature = STANDARD
+            .decode(signature)
+            .map_err(|e| anyhow!("Invalid signature encoding: {e}"))?;
+        if signature.len() != 74 {
+            bail!("Invalid signature length");
+        }
+        let prehashed = match &signature[..2] {
+            b"ED" => true,
+            b"Ed" => false,
+            _ => bail!("Unsupported signature algorithm"),
+        };
+
+        let trusted_comment = trusted_comment
+            .strip_prefix("trusted comment: ")
+            .ok_or_else(|| anyhow!("Invalid signature file: missing trusted comment"))?;
+        let global_signature = STANDARD
+            .decode(global_signature)
+            .map_err(|e| anyhow!("Invalid global signature encoding: {e}"))?;
+
+        Ok(Self {
+            prehashed,
+            key_id: signature[2..10].try_into()?,
+            signature: signature[10..].try_into()?,
+            trusted_comment: trusted_comment.to_string(),
+            global_signature: global_signature
+                .try_into()
+                .map_err(|_| anyhow!("Invalid global signature length"))?,
+        })
+    }
+
+    pub fn trusted_comment(&self) -> &str {
+        &self.trusted_comment
+    }
+
+    /// Verifies the signature of a file with the key matching the signature's key id. Returns the
+    /// key that was used.
+    pub fn verify_file<'a>(
+        &self,
+        path: &Path,
+        keys: &'a [PublicKey],
+    ) -> Result<&'a PublicKey, Error> {
+        let key = keys
+            .iter()
+            .find(|k| k.key_id == self.key_id)
+            .ok_or_else(|| {
+                anyhow!(
+                    "signed with key {}, which is not a trusted key",
+                    key_id_hex(&self.key_id)
+                )
+            })?;
+
+        let mut file =
+            File::open(path).map_err(|e| anyhow!("Cannot open {}: {e}", path.display()))?;
+        let message = if self.prehashed {
+            let mut hasher = Blake2b512::new();
+            let mut buffer = [0u8; 8192];
+            loop {
+                let n = file.read(&mut buffer)?;
+                if n == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..n]);
+            }
+            hasher.finalize().to_vec()
+        } else {
+            let mut content = vec![];
+            file.read_to_end(&mut content)?;
+            content
+        };
+
+        key.key
+            .verify_strict(&message, &Ed25519Signature::from_bytes(&self.signature))
+            .map_err(|_| anyhow!("invalid signature"))?;
+
+        let mut global_message = self.signature.to_vec();
+        global_message.extend_from_slice(self.trusted_comment.as_bytes());
+        key.key
+            .verify_strict(
+                &global_message,
+                &Ed25519Signature::from_bytes(&self.global_signature),
+            )
+            .map_err(|_| anyhow!("invalid signature of the trusted comment"))?;
+
+        Ok(key)
+    }
+}
+
+/// Returns the public keys trusted for a repository, from `trusted_keys.json` in the suiup config
+/// dir. The file maps repositories (e.g. `MystenLabs/sui`) to lists of minisign public keys.
+pub fn trusted_keys(repo: &Repo) -> Result<Vec<PublicKey>, Error> {
+    let path = get_config_file(TRUSTED_KEYS_FILE);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+    let keys: BTreeMap<String, Vec<String>> = serde_json::from_str(&content)
+        .map_err(|e| anyhow!("Cannot parse {}: {e}", path.display()))?;
+
+    keys.get(&repo.to_string())
+        .into_iter()
+        .flatten()
+        .map(|key| {
+            PublicKey::parse(key)
+                .map_err(|e| anyhow!("Invalid key for {repo} in {}: {e}", path.display()))
+        })
+        .collect()
+}
+
+/// Verifies the signature published next to a downloaded release asset, if keys are trusted for
+/// the repository. A downloaded asset that fails verification is deleted. With
+/// `--require-signature`, missing keys or signatures are errors too.
+pub async fn verify_asset_signature(
+    source: &dyn ReleaseSource,
+    repo: &Repo,
+    assets: &[Asset],
+    asset: &Asset,
+    path: &Path,
+    options: &Options,
+) -> Result<(), Error> {
+    let keys = trusted_keys(repo)?;
+    if keys.is_empty() {
+        if options.require_signature {
+            bail!(
+                "No trusted keys configured for {repo} in {} and --require-signature is set",
+                get_config_file(TRUSTED_KEYS_FILE).display()
+            );
+        }
+        return Ok(());
+    }
+
+    let signature_name = format!("{}.{SIGNATURE_EXTENSION}", asset.name);
+    let Some(signature_asset) = assets.iter().find(|a| a.name == signature_name) else {
+        if options.require_signature {
+            bail!("No signature published for {}", asset.name);
+        }
+        options.println(format!(
+            "WARNING: no signature published for {}",
+            asset.name
+        ));
+        return Ok(());
+    };
+
+    let content = fetch_sidecar(source, repo, signature_asset, &asset.name, options).await?;
+    let verified = Signature::parse(&content).and_then(|s| s.verify_file(path, &keys));
+    match verified {
+        Ok(key) => {
+            options.println(format!(
+                "Signature verified for {} (key {})",
+                asset.name,
+                key.key_id()
+            ));
+            Ok(())
+        }
+        Err(e) => {
+            std::fs::remove_file(path)?;
+            bail!("Signature verification failed for {}: {e}", asset.name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const KEY_ID: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+
+    fn public_key(signing_key: &SigningKey) -> String {
+        let mut bytes = b"Ed".to_vec();
+        bytes.extend_from_slice(&KEY_ID);
+        bytes.extend_from_slice(signing_key.verifying_key().as_bytes());
+        format!(
+            "untrusted comment: minisign public key\n{}\n",
+            STANDARD.encode(bytes)
+        )
+    }
+
+    fn sign(signing_key: &SigningKey, data: &[u8]) -> String {
+        let prehash = Blake2b512::digest(data);
+        let signature = signing_key.sign(&prehash).to_bytes();
+        let trusted_comment = "timestamp:1700000000\tfile:sui.tgz";
+
+        let mut signature_bytes = b"ED".to_vec();
+        signature_bytes.extend_from_slice(&KEY_ID);
+        signature_bytes.extend_from_slice(&signature);
+
+        let mut global_message = signature.to_vec();
+        global_message.extend_from_slice(trusted_comment.as_bytes());
+        let global_signature = signing_key.sign(&global_message).to_bytes();
+
+        format!(
+            "untrusted comment: signature from minisign secret key\n{}\ntrusted comment: {trusted_comment}\n{}\n",
+            STANDARD.encode(signature_bytes),
+            STANDARD.encode(global_signature)
+        )
+    }
+
+    #[test]
+    fn test_verify_file() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let key = PublicKey::parse(&public_key(&signing_key)).unwrap();
+        assert_eq!(key.key_id(), "0807060504030201");
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("sui.tgz");
+        std::fs::write(&path, b"release archive").unwrap();
+
+        let signature = Signature::parse(&sign(&signing_key, b"release archive")).unwrap();
+        assert_eq!(
+            signature.trusted_comment(),
+            "timestamp:1700000000\tfile:sui.tgz"
+        );
+        assert!(signature.verify_file(&path, &[key.clone()]).is_ok());
+
+        // tampered file
+        std::fs::write(&path, b"tampered archive").unwrap();
+        assert!(signature.verify_file(&path, &[key]).is_err());
+
+        // untrusted key
+        let other_key = SigningKey::from_bytes(&[9u8; 32]);
+        let mut other = PublicKey::parse(&public_key(&other_key)).unwrap();
+        other.key_id = [0; 8];
+        std::fs::write(&path, b"release archive").unwrap();
+        assert!(signature.verify_file(&path, &[other]).is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(PublicKey::parse("").is_err());
+        assert!(PublicKey::parse("bm90IGEga2V5").is_err());
+        assert!(Signature::parse("untrusted comment: x\nAAAA\n").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Error};
+use serde_yaml::{Mapping, Value};
+
+use crate::handlers::profile::active_profile_config_dir;
+
+pub const SUI_CONFIG_DIR_ENV: &str = "SUI_CONFIG_DIR";
+pub const CLIENT_CONFIG_FILE: &str = "client.yaml";
+
+/// Returns the sui config directory the shims run sui with: `$SUI_CONFIG_DIR`, else the config
+/// dir of the active profile, else `~/.sui/sui_config`
+pub fn sui_config_dir() -> Result<PathBuf, Error> {
+    resolve_sui_config_dir(
+        std::env::var_os(SUI_CONFIG_DIR_ENV).map(PathBuf::from),
+        active_profile_config_dir()?,
+    )
+}
+
+fn resolve_sui_config_dir(
+    env_dir: Option<PathBuf>,
+    profile_dir: Option<PathBuf>,
+) -> Result<PathBuf, Error> {
+    if let Some(dir) = env_dir.or(profile_dir) {
+        return Ok(dir);
+    }
+    let home = dirs::home_dir().ok_or_else(|| anyhow!("Cannot find the home directory"))?;
+    Ok(home.join(".sui").join("sui_config"))
+}
+
+/// Returns the standard fullnode RPC URL for a network
+pub fn default_rpc_url(network: &str) -> Option<&'static str> {
+    match network {
+        "mainnet" => Some("https://fullnode.mainnet.sui.io:443"),
+        "testnet" => Some("https://fullnode.testnet.sui.io:443"),
+        "devnet" => Some("https://fullnode.devnet.sui.io:443"),
+        "localnet" => Some("http://127.0.0.1:9000"),
+        _ => None,
+    }
+}
+
+/// Makes the env with the given alias active in a parsed client.yaml, adding it with the
+/// standard RPC URL if it does not exist. Unknown keys are left untouched. Returns whether the
+/// env was created.
+pub fn set_active_env(config: &mut Value, alias: &str) -> Result<bool, Error> {
+    let config = config
+        .as_mapping_mut()
+        .ok_or_else(|| anyhow!("Invalid client config: expected a mapping"))?;
+
+    let envs = config
+        .entry(Value::from("envs"))
+        .or_insert_with(|| Value::Sequence(vec![]))
+        .as_sequence_mut()
+        .ok_or_else(|| anyhow!("Invalid client config: `envs` is not a list"))?;
+
+    let exists = envs
+        .iter()
+        .any(|env| env.get("alias").and_then(|a| a.as_str()) == Some(alias));
+
+    if !exists {
+        let rpc = default_rpc_url(alias)
+            .ok_or_else(|| anyhow!("No standard RPC URL known for the `{alias}` env"))?;
+        let mut env = Mapping::new();
+        env.insert("alias".into(), alias.into());
+        env.insert("rpc".into(), rpc.into());
+        env.insert("ws".into(), Value::Null);
+        env.insert("basic_auth".into(), Value::Null);
+        envs.push(Value::Mapping(env));
+    }
+
+    config.insert("active_env".into(), alias.into());
+    Ok(!exists)
+}
+
+/// Reads a client.yaml, makes `network` the active env and writes it back. The first sync keeps a
+/// backup of the original file next to it, later ones leave that backup alone.
+pub fn sync_client_env(config_dir: &Path, network: &str) -> Result<(), Error> {
+    let path = config_dir.join(CLIENT_CONFIG_FILE);
+    if !path.exists() {
+        bail!(
+            "Sui client config not found at {}. Run `sui client` once to create it",
+            path.display()
+        );
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+    let mut config: Value = serde_yaml::from_str(&content)
+        .map_err(|e| anyhow!("Cannot parse {}: {e}", path.display()))?;
+
+    let created = set_active_env(&mut config, network)?;
+
+    let backup = path.with_extension("yaml.bak");
+    if !backup.exists() {
+        std::fs::copy(&path, &backup).map_err(|e| {
+            anyhow!(
+                "Cannot back up {} to {}: {e}",
+                path.display(),
+                backup.display()
+            )
+        })?;
+    }
+
+    let content = serde_yaml::to_string(&config)
+        .map_err(|e| anyhow!("Cannot serialize the client config: {e}"))?;
+    std::fs::write(&path, content).map_err(|e| anyhow!("Cannot write {}: {e}", path.display()))?;
+
+    if created {
+        println!("Added `{network}` env to {}", path.display());
+    }
+    println!(
+        "Active sui client env set to `{network}` (original kept at {})",
+        backup.display()
+    );
+    Ok(())
+}
+
+/// Handles `--sync-env`: makes the sui client env matching the network release active
+pub fn handle_sync_env(network: &str) -> Result<(), Error> {
+    if default_rpc_url(network).is_none() {
+        println!("`{network}` is not a network release, not changing the sui client env");
+        return Ok(());
+    }
+    sync_client_env(&sui_config_dir()?, network)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT_YAML: &str = r#"---
+keystore:
+  File: /home/user/.sui/sui_config/sui.keystore
+envs:
+  - alias: mainnet
+    rpc: "https://fullnode.mainnet.sui.io:443"
+    ws: ~
+    basic_auth: ~
+  - alias: testnet
+    rpc: "https://my-testnet-rpc.example.com"
+    ws: ~
+    basic_auth: ~
+active_env: mainnet
+active_address: "0x1234"
+custom_key: keep me
+"#;
+
+    #[test]
+    fn test_set_active_env_existing() {
+        let mut config: Value = serde_yaml::from_str(CLIENT_YAML).unwrap();
+        assert!(!set_active_env(&mut config, "testnet").unwrap());
+        assert_eq!(config["active_env"].as_str(), Some("testnet"));
+        assert_eq!(config["envs"].as_sequence().unwrap().len(), 2);
+        // custom RPC URLs and unknown keys are preserved
+        assert_eq!(
+            config["envs"][1]["rpc"].as_str(),
+            Some("https://my-testnet-rpc.example.com")
+        );
+        assert_eq!(config["custom_key"].as_str(), Some("keep me"));
+        assert_eq!(config["active_address"].as_str(), Some("0x1234"));
+    }
+
+    #[test]
+    fn test_set_active_env_creates_env() {
+        let mut config: Value = serde_yaml::from_str(CLIENT_YAML).unwrap();
+        assert!(set_active_env(&mut config, "devnet").unwrap());
+        assert_eq!(config["active_env"].as_str(), Some("devnet"));
+        assert_eq!(
+            config["envs"][2]["rpc"].as_str(),
+            Some("https://fullnode.devnet.sui.io:443")
+        );
+
+        assert!(set_active_env(&mut config, "some-branch").is_err());
+    }
+
+    #[test]
+    fn test_sync_client_env_writes_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(CLIENT_CONFIG_FILE);
+        std::fs::write(&path, CLIENT_YAML).unwrap();
+
+        sync_client_env(temp_dir.path(), "testnet").unwrap();
+
+        let backup = temp_dir.path().join("client.yaml.bak");
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), CLIENT_YAML);
+        let config: Value = serde_yaml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(config["active_env"].as_str(), Some("testnet"));
+
+        // a second switch keeps the pristine backup
+        sync_client_env(temp_dir.path(), "devnet").unwrap();
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), CLIENT_YAML);
+    }
+
+    #[test]
+    fn test_sync_client_env_active_profile() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let profile_dir = temp_dir.path().join("profile");
+        std::fs::create_dir_all(&profile_dir).unwrap();
+        std::fs::write(profile_dir.join(CLIENT_CONFIG_FILE), CLIENT_YAML).unwrap();
+
+        // the env of the profile's client is changed, like the shims run sui with it
+        let dir = resolve_sui_config_dir(None, Some(profile_dir.clone())).unwrap();
+        assert_eq!(dir, profile_dir);
+        sync_client_env(&dir, "testnet").unwrap();
+        let content = std::fs::read_to_string(profile_dir.join(CLIENT_CONFIG_FILE)).unwrap();
+        let config: Value = serde_yaml::from_str(&content).unwrap();
+        assert_eq!(config["active_env"].as_str(), Some("testnet"));
+
+        // an explicit SUI_CONFIG_DIR wins, the shims do not override it
+        let env_dir = temp_dir.path().join("env");
+        assert_eq!(
+            resolve_sui_config_dir(Some(env_dir.clone()), Some(profile_dir)).unwrap(),
+            env_dir
+        );
+    }
+}
@@ -1,20 +1,20 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::{bail, Result};
 use tracing::info;
 
 use crate::{
-    handlers::update_default_version_file,
-    paths::{binaries_dir, get_default_bin_dir},
+    handlers::{
+        installed_binary_path, shim::install_shim, sui_config::handle_sync_env,
+        update_default_version_file,
+    },
+    paths::get_default_bin_dir,
     types::{BinaryVersion, InstalledBinaries},
 };
 
-#[cfg(not(windows))]
-use std::os::unix::fs::PermissionsExt;
-
 /// Handle the switch command
-pub fn handle_switch(binary_spec: &str) -> Result<()> {
+pub fn handle_switch(binary_spec: &str, sync_env: bool) -> Result<()> {
     // Parse the binary@network_release format
     let (binary_name, network_release) = parse_binary_spec(binary_spec)?;
 
@@ -31,6 +31,10 @@ pub fn handle_switch(binary_spec: &str) -> Result<()> {
         matching_binary.binary_name, matching_binary.version, matching_binary.network_release
     );
 
+    if sync_env {
+        handle_sync_env(&matching_binary.network_release)?;
+    }
+
     Ok(())
 }
 
@@ -82,17 +86,21 @@ pub fn find_matching_binary(
     Ok(matching_binaries[0].clone())
 }
 
-/// Switch to the specified binary by copying it to the default bin directory
-fn switch_to_binary(binary: &BinaryVersion) -> Result<()> {
-    let src = get_binary_source_path(binary);
+/// Switch to the specified binary by pointing its shim in the default bin directory to it
+pub fn switch_to_binary(binary: &BinaryVersion) -> Result<()> {
+    let src = installed_binary_path(binary);
     let dst = get_binary_destination_path(binary);
 
-    // Copy the binary file
-    copy_binary_file(&src, &dst, &binary.binary_name)?;
+    if !src.exists() {
+        bail!(
+            "Cannot switch to {}, binary not found: {}",
+            binary.binary_name,
+            src.display()
+        );
+    }
 
-    // Set executable permissions on Unix systems
-    #[cfg(unix)]
-    set_executable_permissions(&dst)?;
+    info!("Placing shim for {} at {}", src.display(), dst.display());
+    install_shim(&dst)?;
 
     // Update the default version file
     update_default_version_file(
@@ -105,32 +113,8 @@ fn switch_to_binary(binary: &BinaryVersion) -> Result<()> {
     Ok(())
 }
 
-/// Construct the source path for a binary
-fn get_binary_source_path(binary: &BinaryVersion) -> std::path::PathBuf {
-    let mut src = binaries_dir();
-    src.push(&binary.network_release);
-
-    // Handle nightly builds which have a different directory structure
-    if binary.version == "nightly" {
-        src.push("bin");
-    }
-
-    let binary_filename = if binary.debug {
-        format!("{}-debug-{}", binary.binary_name, binary.version)
-    } else {
-        format!("{}-{}", binary.binary_name, binary.version)
-    };
-
-    src.push(&binary_filename);
-
-    #[cfg(target_os = "windows")]
-    src.set_extension("exe");
-
-    src
-}
-
 /// Construct the destination path for a binary
-fn get_binary_destination_path(binary: &BinaryVersion) -> std::path::PathBuf {
+pub fn get_binary_destination_path(binary: &BinaryVersion) -> std::path::PathBuf {
     let mut dst = get_default_bin_dir();
     let dst_name = if binary.debug {
         format!("{}-debug", binary.binary_name)
@@ -145,35 +129,3 @@ fn get_binary_destination_path(binary: &BinaryVersion) -> std::path::PathBuf {
 
     dst
 }
-
-/// Copy binary file from source to destination
-fn copy_binary_file(src: &std::path::Path, dst: &std::path::Path, binary_name: &str) -> Result<()> {
-    info!("Copying from {} to {}", src.display(), dst.display());
-
-    // Remove existing file if it exists
-    if dst.exists() {
-        std::fs::remove_file(dst)?;
-    }
-
-    // Copy the binary
-    std::fs::copy(src, dst).map_err(|e| {
-        anyhow!(
-            "Error copying {} to the default folder (src: {}, dst: {}): {}",
-            binary_name,
-            src.display(),
-            dst.display(),
-            e
-        )
-    })?;
-
-    Ok(())
-}
-
-/// Set executable permissions on Unix systems
-#[cfg(unix)]
-fn set_executable_permissions(path: &std::path::Path) -> Result<()> {
-    let mut perms = std::fs::metadata(path)?.permissions();
-    perms.set_mode(0o755);
-    std::fs::set_permissions(path, perms)?;
-    Ok(())
-}
@@ -0,0 +1,330 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Error};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::{parse_component_with_version, print_table, BinaryName, CommandMetadata};
+use crate::handlers::available_components;
+use crate::handlers::release::ensure_version_prefix;
+use crate::handlers::version::compare_versions;
+use crate::types::{BinaryVersion, InstalledBinaries};
+
+/// File names that are recognized as a project toolchain file, in order of precedence.
+pub const TOOLCHAIN_FILE_NAMES: &[&str] = &["suiup.toml", "sui-toolchain.toml"];
+
+/// Contents of a `suiup.toml` toolchain file, which pins binaries to a network/version.
+///
+/// ```toml
+/// [toolchain]
+/// sui = "testnet-1.39.3"
+/// walrus = "testnet"
+/// mvr = "0.0.8"
+/// ```
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ToolchainFile {
+    #[serde(default)]
+    pub toolchain: BTreeMap<String, String>,
+}
+
+/// A toolchain file found on disk together with its parsed contents
+#[derive(Debug, Clone)]
+pub struct ActiveToolchain {
+    pub path: PathBuf,
+    pub file: ToolchainFile,
+}
+
+/// A single pin from a toolchain file, e.g. `sui = "testnet-1.39.3"`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolchainPin {
+    pub binary_name: String,
+    pub network: String,
+    pub version: Option<String>,
+}
+
+impl ToolchainFile {
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read toolchain file {}: {e}", path.display()))?;
+        let file: ToolchainFile = toml::from_str(&content)
+            .map_err(|e| anyhow!("Cannot parse toolchain file {}: {e}", path.display()))?;
+        for binary in file.toolchain.keys() {
+            if !available_components().contains(&binary.as_str()) {
+                bail!(
+                    "Unknown binary `{binary}` in toolchain file {}. Use `suiup list` to find available binaries.",
+                    path.display()
+                );
+            }
+        }
+        Ok(file)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), Error> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| anyhow!("Cannot serialize toolchain file: {e}"))?;
+        std::fs::write(path, content)
+            .map_err(|e| anyhow!("Cannot write toolchain file {}: {e}", path.display()))?;
+        Ok(())
+    }
+
+    /// Returns the parsed pin for the given binary, if any
+    pub fn pin(&self, binary: &str) -> Result<Option<ToolchainPin>, Error> {
+        self.toolchain
+            .get(binary)
+            .map(|spec| parse_pin(binary, spec))
+            .transpose()
+    }
+
+    pub fn pins(&self) -> Result<Vec<ToolchainPin>, Error> {
+        self.toolchain
+            .iter()
+            .map(|(binary, spec)| parse_pin(binary, spec))
+            .collect()
+    }
+}
+
+/// Parses a toolchain pin value (e.g. `testnet-1.39.3`, `testnet`, `1.39.3`) for a binary
+pub fn parse_pin(binary: &str, spec: &str) -> Result<ToolchainPin, Error> {
+    let CommandMetadata {
+        name,
+        network,
+        version,
+    } = parse_component_with_version(&format!("{binary}@{spec}"))?;
+
+    // mvr is a standalone binary, not tied to a network
+    let network = if name == BinaryName::Mvr {
+        "standalone".to_string()
+    } else {
+        network
+    };
+
+    Ok(ToolchainPin {
+        binary_name: name.to_string(),
+        network,
+        version: version.map(|v| ensure_version_prefix(&v)),
+    })
+}
+
+/// Walks up from `start` and returns the first toolchain file found
+pub fn find_toolchain_file(start: &Path) -> Option<PathBuf> {
+    start.ancestors().find_map(|dir| {
+        TOOLCHAIN_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    })
+}
+
+/// Loads the toolchain file that applies to the current directory, if any
+pub fn load_active_toolchain() -> Result<Option<ActiveToolchain>, Error> {
+    let cwd = std::env::current_dir()?;
+    match find_toolchain_file(&cwd) {
+        Some(path) => {
+            let file = ToolchainFile::read(&path)?;
+            Ok(Some(ActiveToolchain { path, file }))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Finds the installed binary matching a pin, a debug build if `debug` is set. If the pin has no
+/// version, the highest installed version for that network is used.
+pub fn resolve_pin(
+    installed_binaries: &InstalledBinaries,
+    pin: &ToolchainPin,
+    debug: bool,
+) -> Option<BinaryVersion> {
+    installed_binaries
+        .binaries()
+        .iter()
+        .filter(|b| {
+            b.binary_name == pin.binary_name
+                && b.network_release == pin.network
+                && b.debug == debug
+                && pin.version.as_ref().is_none_or(|v| &b.version == v)
+        })
+        .max_by(|a, b| compare_versions(&a.version, &b.version))
+        .cloned()
+}
+
+/// Returns the path of the toolchain file to edit: the one in the current directory if it
+/// exists, otherwise a new `suiup.toml` in the current directory.
+fn local_toolchain_file() -> Result<PathBuf, Error> {
+    let cwd = std::env::current_dir()?;
+    Ok(TOOLCHAIN_FILE_NAMES
+        .iter()
+        .map(|name| cwd.join(name))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| cwd.join(TOOLCHAIN_FILE_NAMES[0])))
+}
+
+/// Handles the `toolchain set` command
+pub fn handle_toolchain_set(specs: &[String]) -> Result<(), Error> {
+    let path = local_toolchain_file()?;
+    let mut file = if path.exists() {
+        ToolchainFile::read(&path)?
+    } else {
+        ToolchainFile::default()
+    };
+
+    for spec in specs {
+        let (binary, version) = spec.split_once('@').ok_or_else(|| {
+            anyhow!("Invalid format `{spec}`. Use 'binary@version', e.g. 'sui@testnet-1.39.3'")
+        })?;
+        // validate the spec before writing it
+        let pin = parse_pin(binary, version)?;
+        file.toolchain
+            .insert(pin.binary_name.clone(), version.to_string());
+        println!("Pinned {} to {version}", pin.binary_name);
+    }
+
+    file.write(&path)?;
+    println!("Toolchain file written to {}", path.display());
+    Ok(())
+}
+
+/// Handles the `toolchain unset` command. Without binaries, the toolchain file is removed.
+pub fn handle_toolchain_unset(binaries: &[String]) -> Result<(), Error> {
+    let path = local_toolchain_file()?;
+    if !path.exists() {
+        bail!("No toolchain file found in the current directory");
+    }
+
+    if binaries.is_empty() {
+        std::fs::remove_file(&path)
+            .map_err(|e| anyhow!("Cannot remove {}: {e}", path.display()))?;
+        println!("Removed toolchain file {}", path.display());
+        return Ok(());
+    }
+
+    let mut file = ToolchainFile::read(&path)?;
+    for binary in binaries {
+        if file.toolchain.remove(binary).is_none() {
+            println!("{binary} is not pinned in {}", path.display());
+        } else {
+            println!("Removed pin for {binary}");
+        }
+    }
+
+    if file.toolchain.is_empty() {
+        std::fs::remove_file(&path)
+            .map_err(|e| anyhow!("Cannot remove {}: {e}", path.display()))?;
+        println!("No pins left, removed toolchain file {}", path.display());
+    } else {
+        file.write(&path)?;
+    }
+    Ok(())
+}
+
+/// Handles the `toolchain show` command
+pub fn handle_toolchain_show() -> Result<(), Error> {
+    match load_active_toolchain()? {
+        Some(active) => print_active_toolchain(&active),
+        None => {
+            println!("No toolchain file found in the current directory or its parents");
+            Ok(())
+        }
+    }
+}
+
+/// Prints which toolchain file is active and the binaries it resolves to
+pub fn print_active_toolchain(active: &ActiveToolchain) -> Result<(), Error> {
+    let installed_binaries = InstalledBinaries::new()?;
+    println!(
+        "\x1b[1mToolchain pins (from {}):\x1b[0m",
+        active.path.display()
+    );
+
+    let mut resolved = vec![];
+    for pin in active.file.pins()? {
+        match resolve_pin(&installed_binaries, &pin, false) {
+            Some(binary) => resolved.push(binary),
+            None => {
+                let spec = &active.file.toolchain[&pin.binary_name];
+                println!(
+                    "WARNING: {}@{spec} is pinned but not installed. Run `suiup install {}@{spec}`",
+                    pin.binary_name, pin.binary_name
+                );
+            }
+        }
+    }
+    print_table(&resolved);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pin() {
+        let pin = parse_pin("sui", "testnet-1.39.3").unwrap();
+        assert_eq!(pin.network, "testnet");
+        assert_eq!(pin.version, Some("v1.39.3".to_string()));
+
+        let pin = parse_pin("walrus", "mainnet").unwrap();
+        assert_eq!(pin.network, "mainnet");
+        assert_eq!(pin.version, None);
+
+        let pin = parse_pin("mvr", "0.0.8").unwrap();
+        assert_eq!(pin.network, "standalone");
+        assert_eq!(pin.version, Some("v0.0.8".to_string()));
+
+        assert!(parse_pin("random", "testnet").is_err());
+    }
+
+    #[test]
+    fn test_resolve_pin_latest_version() {
+        let binary = |version: &str, debug: bool| BinaryVersion {
+            binary_name: "sui".to_string(),
+            network_release: "testnet".to_string(),
+            version: version.to_string(),
+            debug,
+            path: None,
+            sha256: None,
+            provenance: None,
+        };
+        let installed: InstalledBinaries = serde_json::from_value(serde_json::json!({
+            "binaries": [
+                binary("v1.9.0", false),
+                binary("v1.10.0", false),
+                binary("v1.2.0", false),
+                binary("v1.10.0", true),
+            ]
+        }))
+        .unwrap();
+        let pin = parse_pin("sui", "testnet").unwrap();
+        let resolved = resolve_pin(&installed, &pin, false).unwrap();
+        assert_eq!(resolved.version, "v1.10.0");
+        assert!(!resolved.debug);
+
+        // the debug build is only picked when asked for
+        let pin = parse_pin("sui", "testnet-1.10.0").unwrap();
+        assert!(resolve_pin(&installed, &pin, true).unwrap().debug);
+        assert!(!resolve_pin(&installed, &pin, false).unwrap().debug);
+        let pin = parse_pin("sui", "testnet-1.9.0").unwrap();
+        assert!(resolve_pin(&installed, &pin, true).is_none());
+    }
+
+    #[test]
+    fn test_find_toolchain_file_walks_up() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let nested = temp_dir.path().join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_toolchain_file(&nested), None);
+
+        let file = temp_dir.path().join("sui-toolchain.toml");
+        std::fs::write(&file, "[toolchain]\nsui = \"testnet\"\n").unwrap();
+        assert_eq!(find_toolchain_file(&nested), Some(file));
+
+        let closer = temp_dir.path().join("a").join("suiup.toml");
+        std::fs::write(&closer, "[toolchain]\nsui = \"devnet\"\n").unwrap();
+        assert_eq!(find_toolchain_file(&nested), Some(closer.clone()));
+
+        let file = ToolchainFile::read(&closer).unwrap();
+        assert_eq!(file.toolchain["sui"], "devnet");
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::options::Options;
 use crate::{
     commands::{parse_component_with_version, BinaryName, CommandMetadata, ComponentCommands},
     handle_commands::handle_cmd,
@@ -16,11 +17,7 @@ use crate::{
 use anyhow::{bail, Error};
 
 /// Handles the `update` command
-pub async fn handle_update(
-    binary_name: String,
-    yes: bool,
-    github_token: Option<String>,
-) -> Result<(), Error> {
+pub async fn handle_update(binary_name: String, yes: bool, options: &Options) -> Result<(), Error> {
     if binary_name.is_empty() {
         bail!("Invalid number of arguments for `update` command");
     }
@@ -70,12 +67,12 @@ pub async fn handle_update(
     if name == BinaryName::Mvr {
         handle_cmd(
             ComponentCommands::Add {
-                component: binary_name,
+                components: vec![binary_name],
                 debug: false,
                 nightly: None,
                 yes,
             },
-            github_token,
+            options,
         )
         .await?;
         return Ok(());
@@ -84,18 +81,18 @@ pub async fn handle_update(
     if name == BinaryName::Walrus {
         handle_cmd(
             ComponentCommands::Add {
-                component: binary_name,
+                components: vec![binary_name],
                 debug: false,
                 nightly: None,
                 yes,
             },
-            github_token,
+            options,
         )
         .await?;
         return Ok(());
     }
 
-    let releases = release_list(&Repo::Sui, github_token.clone()).await?.0;
+    let releases = release_list(&Repo::Sui, options).await?;
     let mut to_update = vec![];
     for (n, v) in &network_local_last_version {
         let last_release = last_release_for_network(&releases, n).await?;
@@ -112,12 +109,12 @@ pub async fn handle_update(
         println!("Updating {name} to {v} from {n} release");
         handle_cmd(
             ComponentCommands::Add {
-                component: binary_name.clone(),
+                components: vec![binary_name.clone()],
                 debug: false,
                 nightly: None,
                 yes,
             },
-            github_token.clone(),
+            options,
         )
         .await?;
     }
@@ -0,0 +1,277 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Error};
+
+use crate::handlers::checksum::sha256_file;
+use crate::handlers::installed_binary_path;
+use crate::handlers::shim::default_shim_path;
+use crate::handlers::version::parse_version;
+use crate::paths::{default_file_path, get_default_bin_dir};
+use crate::types::{BinaryVersion, InstalledBinaries, Version};
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> Result<bool, Error> {
+    use std::os::unix::fs::PermissionsExt;
+    Ok(std::fs::metadata(path)?.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> Result<bool, Error> {
+    Ok(true)
+}
+
+/// Whether a `--version` output reports exactly `version`, e.g. `sui 1.40.1-5f1b3b7e` reports
+/// `v1.40.1`, but not `v1.4.1`
+fn reports_version(reported: &str, version: &str) -> bool {
+    let Some(expected) = parse_version(version) else {
+        return false;
+    };
+    reported.split_whitespace().any(|token| {
+        // drop the commit or build metadata after the version
+        let token = token.split(['-', '+']).next().unwrap_or_default();
+        token.split('.').count() == 3 && parse_version(token) == Some(expected)
+    })
+}
+
+/// Runs `<binary> --version` and checks that it reports `version`
+fn check_reported_version(path: &Path, version: &str) -> Option<String> {
+    let output = match Command::new(path).arg("--version").output() {
+        Ok(output) => output,
+        Err(e) => return Some(format!("cannot run `--version`: {e}")),
+    };
+    let reported = String::from_utf8_lossy(&output.stdout);
+    if reports_version(&reported, version) {
+        None
+    } else {
+        Some(format!(
+            "`--version` reports `{}`, expected {version}",
+            reported.trim()
+        ))
+    }
+}
+
+/// Checks an installed binary against its recorded metadata and returns the problems found. The
+/// binary is looked up where it is registered and hashed, which for debug builds differs from the
+/// recorded path.
+pub fn check_installed_binary(binary: &BinaryVersion) -> Result<Vec<String>, Error> {
+    check_binary_file(binary, &installed_binary_path(binary))
+}
+
+fn check_binary_file(binary: &BinaryVersion, path: &Path) -> Result<Vec<String>, Error> {
+    if !path.exists() {
+        return Ok(vec![format!("{} does not exist", path.display())]);
+    }
+
+    let mut problems = vec![];
+    if !is_executable(path)? {
+        problems.push(format!("{} is not executable", path.display()));
+    }
+
+    if let Some(expected) = &binary.sha256 {
+        let actual = sha256_file(path)?;
+        if &actual != expected {
+            problems.push(format!(
+                "SHA-256 is {actual}, but {expected} was recorded at install time"
+            ));
+        }
+    }
+
+    // nightly builds do not report a release version
+    if problems.is_empty() && binary.version != "nightly" {
+        problems.extend(check_reported_version(path, &binary.version));
+    }
+    Ok(problems)
+}
+
+/// Checks that a default binary entry is served by a suiup shim and points to an installed binary
+fn check_default_binary(
+    binary: &BinaryVersion,
+    installed_binaries: &InstalledBinaries,
+    suiup_sha256: &str,
+) -> Result<Vec<String>, Error> {
+    let mut problems = vec![];
+    let shim = default_shim_path(binary);
+    if !shim.exists() {
+        problems.push(format!("{} does not exist", shim.display()));
+    } else {
+        let actual = sha256_file(&shim)?;
+        if actual != suiup_sha256 {
+            let installed = installed_binary_path(binary);
+            if installed.exists() && sha256_file(&installed)? == actual {
+                problems.push(format!(
+                    "{} is a copy of the binary instead of a suiup shim. Run `suiup switch {}@{}` to fix it",
+                    shim.display(),
+                    binary.binary_name,
+                    binary.network_release
+                ));
+            } else {
+                problems.push(format!(
+                    "{} was overwritten by another binary (e.g. by `cargo install`)",
+                    shim.display()
+                ));
+            }
+        }
+    }
+
+    let is_installed = installed_binaries.binaries().iter().any(|b| {
+        b.binary_name == binary.binary_name
+            && b.network_release == binary.network_release
+            && b.version == binary.version
+            && b.debug == binary.debug
+    });
+    if !is_installed {
+        problems.push(format!(
+            "default {binary} from {} is not in the installed binaries",
+            binary.network_release
+        ));
+    }
+
+    // the shim is only used if no other binary with the same name comes first on the PATH
+    if let Some(first) = find_on_path(&shim) {
+        if first != shim {
+            problems.push(format!(
+                "{} comes first on the PATH and shadows {}",
+                first.display(),
+                shim.display()
+            ));
+        }
+    }
+    Ok(problems)
+}
+
+/// Returns the first file on the PATH with the same name as `path`
+fn find_on_path(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?;
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+fn print_result(what: &str, problems: &[String]) {
+    if problems.is_empty() {
+        println!("[OK]   {what}");
+    } else {
+        println!("[FAIL] {what}");
+        for problem in problems {
+            println!("       - {problem}");
+        }
+    }
+}
+
+/// Handles the `verify` command: audits the installed and default binaries against the recorded
+/// metadata
+pub fn handle_verify() -> Result<(), Error> {
+    let installed_binaries = InstalledBinaries::new()?;
+    let mut failures = 0;
+
+    println!("\x1b[1mInstalled binaries:\x1b[0m");
+    for binary in installed_binaries.binaries() {
+        let problems = check_installed_binary(binary)?;
+        if !problems.is_empty() {
+            failures += 1;
+        }
+        print_result(
+            &format!("{binary} from {}", binary.network_release),
+            &problems,
+        );
+    }
+
+    let content = std::fs::read_to_string(default_file_path()?)?;
+    let default: BTreeMap<String, (String, Version, bool)> = serde_json::from_str(&content)
+        .map_err(|_| {
+            anyhow!("Cannot decode default binary file to JSON. Is the file corrupted?")
+        })?;
+    let suiup =
+        std::env::current_exe().map_err(|e| anyhow!("Cannot find the suiup executable: {e}"))?;
+    let suiup_sha256 = sha256_file(&suiup)?;
+
+    println!(
+        "\x1b[1mDefault binaries in {}:\x1b[0m",
+        get_default_bin_dir().display()
+    );
+    for (name, (network, version, debug)) in default {
+        let binary = BinaryVersion {
+            binary_name: name,
+            network_release: network,
+            version,
+            debug,
+            path: None,
+            sha256: None,
+            provenance: None,
+        };
+        let problems = check_default_binary(&binary, &installed_binaries, &suiup_sha256)?;
+        if !problems.is_empty() {
+            failures += 1;
+        }
+        print_result(
+            &format!("{binary} from {}", binary.network_release),
+            &problems,
+        );
+    }
+
+    if failures > 0 {
+        bail!("{failures} binaries failed verification");
+    }
+    println!("All binaries verified");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binary(version: &str, sha256: Option<String>) -> BinaryVersion {
+        BinaryVersion {
+            binary_name: "sui".to_string(),
+            network_release: "testnet".to_string(),
+            version: version.to_string(),
+            debug: false,
+            path: None,
+            sha256,
+            provenance: None,
+        }
+    }
+
+    #[test]
+    fn test_reports_version() {
+        assert!(reports_version("sui 1.40.1-5f1b3b7e\n", "v1.40.1"));
+        assert!(reports_version("walrus v1.18.2", "1.18.2"));
+        assert!(!reports_version("sui 11.4.1-5f1b3b7e", "v1.4.1"));
+        assert!(!reports_version("sui 1.4.10", "v1.4.1"));
+        assert!(!reports_version("sui 1.40", "v1.40.0"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_installed_binary() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("sui-v1.40.1");
+        std::fs::write(&path, "#!/bin/sh\necho sui 1.40.1-5f1b3b7e\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let sha256 = sha256_file(&path).unwrap();
+
+        let ok = binary("v1.40.1", Some(sha256.clone()));
+        assert!(check_binary_file(&ok, &path).unwrap().is_empty());
+
+        let wrong_version = binary("v1.41.0", Some(sha256));
+        let problems = check_binary_file(&wrong_version, &path).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("expected v1.41.0"));
+
+        let wrong_hash = binary("v1.40.1", Some("0".repeat(64)));
+        let problems = check_binary_file(&wrong_hash, &path).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("SHA-256"));
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let problems = check_binary_file(&ok, &path).unwrap();
+        assert!(problems[0].contains("not executable"));
+    }
+}
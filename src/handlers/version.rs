@@ -1,8 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::cmp::Ordering;
+
 use anyhow::{anyhow, Error};
 
+/// Numeric components of a version: major, minor and patch
+pub type VersionNumber = [u64; 3];
+
 /// Extracts the version from a release filename
 pub fn extract_version_from_release(release: &str) -> Result<String, Error> {
     let re = regex::Regex::new(r"v\d+\.\d+\.\d+").unwrap();
@@ -12,3 +17,44 @@ pub fn extract_version_from_release(release: &str) -> Result<String, Error> {
 
     Ok(captures.get(0).unwrap().as_str().to_string())
 }
+
+/// Parses a version such as `1.40.1` or `v1.40`, missing components being 0
+pub fn parse_version(s: &str) -> Option<VersionNumber> {
+    let s = s.trim();
+    let s = s.strip_prefix('v').unwrap_or(s);
+    let mut version = [0; 3];
+    let parts: Vec<&str> = s.split('.').collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    for (i, part) in parts.iter().enumerate() {
+        version[i] = part.parse().ok()?;
+    }
+    Some(version)
+}
+
+/// Compares two versions numerically, so that `v1.10.0` is newer than `v1.9.0`. Versions that
+/// are not numbers, e.g. `nightly`, are older than numbered ones and compared by name.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    match (parse_version(a), parse_version(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => a.cmp(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("v1.10.0", "v1.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.9.0", "1.10.0"), Ordering::Less);
+        assert_eq!(compare_versions("v1.40.1", "1.40.1"), Ordering::Equal);
+        assert_eq!(compare_versions("nightly", "v0.0.1"), Ordering::Less);
+        assert_eq!(parse_version("v1.40"), Some([1, 40, 0]));
+        assert_eq!(parse_version("1.2.3.4"), None);
+    }
+}
@@ -3,8 +3,11 @@
 
 pub mod commands;
 pub mod component;
+pub mod config;
 pub mod handle_commands;
 pub mod handlers;
 pub mod mvr;
+pub mod options;
 pub mod paths;
+pub mod source;
 pub mod types;
@@ -3,6 +3,7 @@
 
 use clap::Parser;
 use suiup::commands::Command;
+use suiup::handlers::shim::{invoked_shim_name, run_shim};
 use suiup::paths::initialize;
 
 #[tokio::main]
@@ -10,6 +11,15 @@ async fn main() -> anyhow::Result<()> {
     env_logger::init();
     initialize()?;
 
+    // Invoked through a shim in the default bin dir (e.g. as `sui`), run the resolved binary
+    if let Some(name) = invoked_shim_name() {
+        if let Err(err) = run_shim(&name, std::env::args_os().skip(1)) {
+            eprintln!("Error: {}", err);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let cmd = Command::parse();
     if let Err(err) = cmd.exec().await {
         eprintln!("Error: {}", err);
@@ -3,27 +3,26 @@
 
 // use crate::handle_commands::{binaries_folder, detect_os_arch, download_file};
 use crate::{
-    handlers::download::{detect_os_arch, download_file},
+    handlers::download::{detect_os_arch, download_verified_asset},
+    options::Options,
     paths::binaries_dir,
-    types::Repo,
+    source::release_source,
+    types::{Provenance, Release, Repo},
 };
 use anyhow::{anyhow, Error};
-use serde::Deserialize;
-
-#[derive(Deserialize, Debug)]
-pub struct MvrRelease {
-    pub tag_name: String,
-    pub assets: Vec<MvrAsset>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct MvrAsset {
-    pub name: String,
-    pub browser_download_url: String,
+use std::path::PathBuf;
+
+/// Returns the path an mvr version is installed to in the binaries folder
+pub fn mvr_binary_path(version: &str) -> PathBuf {
+    #[cfg(not(windows))]
+    let filename = format!("mvr-{version}");
+    #[cfg(target_os = "windows")]
+    let filename = format!("mvr-{version}.exe");
+    binaries_dir().join("standalone").join(filename)
 }
 
 pub struct MvrInstaller {
-    releases: Vec<MvrRelease>,
+    releases: Vec<Release>,
 }
 
 impl Default for MvrInstaller {
@@ -39,26 +38,21 @@ impl MvrInstaller {
         }
     }
 
-    pub async fn get_releases(&mut self) -> Result<(), Error> {
-        let client = reqwest::Client::new();
-        let url = format!("https://api.github.com/repos/{}/releases", Repo::Mvr);
+    /// Creates an installer picking releases from an already fetched release list
+    pub fn with_releases(releases: Vec<Release>) -> Self {
+        Self { releases }
+    }
 
+    pub async fn get_releases(&mut self, options: &Options) -> Result<(), Error> {
         if !self.releases.is_empty() {
             return Ok(());
         }
 
-        let releases: Vec<MvrRelease> = client
-            .get(&url)
-            .header("User-Agent", "suiup")
-            .send()
-            .await?
-            .json()
-            .await?;
-        self.releases = releases;
+        self.releases = release_source(options).list_releases(&Repo::Mvr).await?;
         Ok(())
     }
 
-    pub fn get_latest_release(&self) -> Result<&MvrRelease, Error> {
+    pub fn get_latest_release(&self) -> Result<&Release, Error> {
         println!("Downloading release list");
         let releases = &self.releases;
         releases
@@ -66,17 +60,24 @@ impl MvrInstaller {
             .ok_or_else(|| anyhow!("No MVR releases found"))
     }
 
-    /// Download the MVR CLI binary, if it does not exist in the binary folder.
-    pub async fn download_version(&mut self, version: Option<String>) -> Result<String, Error> {
+    /// Download the MVR CLI binary, if it does not exist in the binary folder. Returns the version
+    /// and, if the binary was downloaded, the release asset it comes from.
+    pub async fn download_version(
+        &mut self,
+        version: Option<String>,
+        options: &Options,
+    ) -> Result<(String, Option<Provenance>), Error> {
         let version = if let Some(v) = version {
             // Ensure version has 'v' prefix for GitHub release tags
             crate::handlers::release::ensure_version_prefix(&v)
         } else {
             if self.releases.is_empty() {
-                self.get_releases().await?;
+                self.get_releases(options).await?;
             }
             let latest_release = self.get_latest_release()?.tag_name.clone();
-            println!("No version specified. Downloading latest release: {latest_release}");
+            options.println(format!(
+                "No version specified. Downloading latest release: {latest_release}"
+            ));
             latest_release
         };
 
@@ -84,18 +85,15 @@ impl MvrInstaller {
         if !cache_folder.exists() {
             std::fs::create_dir_all(&cache_folder)?;
         }
-        #[cfg(not(windows))]
-        let mvr_binary_path = cache_folder.join(format!("mvr-{}", version));
-        #[cfg(target_os = "windows")]
-        let mvr_binary_path = cache_folder.join(format!("mvr-{}.exe", version));
+        let mvr_binary_path = mvr_binary_path(&version);
 
         if mvr_binary_path.exists() {
-            println!("Binary mvr-{version} already installed. Use `suiup default set mvr {version}` to set the default version to the desired one");
-            return Ok(version);
+            options.println(format!("Binary mvr-{version} already installed. Use `suiup default set mvr {version}` to set the default version to the desired one"));
+            return Ok((version, None));
         }
 
         if self.releases.is_empty() {
-            self.get_releases().await?;
+            self.get_releases(options).await?;
         }
 
         let release = self
@@ -116,11 +114,14 @@ impl MvrInstaller {
             .find(|a| a.name.starts_with(&asset_name))
             .ok_or_else(|| anyhow!("No compatible binary found for your system"))?;
 
-        download_file(
-            &asset.browser_download_url,
+        let source = release_source(options);
+        let asset_sha256 = download_verified_asset(
+            source.as_ref(),
+            &Repo::Mvr,
+            &release.assets,
+            asset,
             &mvr_binary_path,
-            format!("mvr-{}", version).as_str(),
-            None,
+            options,
         )
         .await?;
 
@@ -132,6 +133,12 @@ impl MvrInstaller {
             std::fs::set_permissions(&mvr_binary_path, perms)?;
         }
 
-        Ok(version)
+        let provenance = Provenance {
+            tag: release.tag_name.clone(),
+            asset: asset.name.clone(),
+            url: source.asset_url(&Repo::Mvr, asset),
+            asset_sha256,
+        };
+        Ok((version, Some(provenance)))
     }
 }
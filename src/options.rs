@@ -0,0 +1,50 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Settings of a command run, taken from the command line and config.toml, that are passed down
+//! to where releases are fetched and release assets are downloaded and verified
+
+use indicatif::MultiProgress;
+use reqwest::Client;
+
+use crate::config::GithubConfig;
+use crate::source::{Credentials, Mirror};
+
+/// Settings affecting where releases come from and how downloads are verified
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// HTTP client of all requests, configured with the proxy, CA bundle and timeouts
+    pub client: Client,
+    /// Credentials of the hosts releases are downloaded from, including the GitHub token
+    pub credentials: Credentials,
+    /// GitHub instances and repositories releases are fetched from
+    pub github: GithubConfig,
+    /// Mirror to fetch releases and assets from instead of GitHub
+    pub mirror: Option<Mirror>,
+    /// Forbid network access: releases and assets are then served from the caches, or from a
+    /// mirror directory
+    pub offline: bool,
+    /// Fail downloads of release assets that have no published SHA-256 checksum
+    pub require_checksum: bool,
+    /// Fail downloads of release assets that are not signed by a key trusted for their repository
+    pub require_signature: bool,
+    /// Progress bars of concurrent downloads, where each download gets a line
+    pub progress: Option<MultiProgress>,
+    /// Send each request once, without retries or retry notices, for background checks that must
+    /// not hold up or clutter the command being run
+    pub best_effort: bool,
+}
+
+impl Options {
+    /// Prints a line above the progress bars while they are shown, so that the bars are not
+    /// garbled, and to stdout otherwise
+    pub fn println(&self, line: impl AsRef<str>) {
+        match &self.progress {
+            Some(progress) if !progress.is_hidden() => {
+                // like println!, a line that cannot be written to the terminal is lost
+                let _ = progress.println(line);
+            }
+            _ => println!("{}", line.as_ref()),
+        }
+    }
+}
@@ -168,6 +168,11 @@ pub fn binaries_dir() -> PathBuf {
     get_suiup_data_dir().join("binaries")
 }
 
+/// Returns the path to the folder holding the suiup-managed sui config profiles
+pub fn profiles_dir() -> PathBuf {
+    get_suiup_data_dir().join("profiles")
+}
+
 pub fn initialize() -> Result<(), Error> {
     create_dir_all(get_suiup_config_dir())?;
     create_dir_all(get_suiup_data_dir())?;
@@ -0,0 +1,290 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Credentials for the hosts releases are downloaded from, keyed by exact host name. They are
+//! collected from `--github-token`/`GITHUB_TOKEN`, the GitHub CLI's `hosts.yml` and `~/.netrc`.
+//! A request only carries the credential of its own host, and the HTTP client drops the
+//! `Authorization` header when following a redirect to another host, such as
+//! objects.githubusercontent.com or Google Storage.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use base64::Engine;
+use serde::Deserialize;
+use tracing::debug;
+
+use super::github::configured_hosts;
+use crate::config::GithubConfig;
+
+/// Hosts a token given with `--github-token` or `GITHUB_TOKEN` is sent to, next to the
+/// configured GitHub instances
+const GITHUB_HOSTS: [&str; 2] = ["github.com", "api.github.com"];
+
+/// A credential for one host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credential {
+    /// A GitHub token, sent as `Authorization: token <token>`
+    Token(String),
+    /// A login and password from `.netrc`, sent with basic authentication
+    Basic { login: String, password: String },
+}
+
+impl Credential {
+    /// Returns the value of the `Authorization` header
+    pub fn authorization(&self) -> String {
+        match self {
+            Credential::Token(token) => format!("token {token}"),
+            Credential::Basic { login, password } => format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(format!("{login}:{password}"))
+            ),
+        }
+    }
+}
+
+/// Credentials keyed by exact host name
+#[derive(Debug, Clone, Default)]
+pub struct Credentials {
+    hosts: HashMap<String, Credential>,
+}
+
+impl Credentials {
+    /// Collects the credentials of all sources. For a host found in several sources, the GitHub
+    /// token wins over the GitHub CLI, which wins over `.netrc`.
+    pub fn load(github_token: Option<String>, github: &GithubConfig) -> Self {
+        let mut credentials = Self::default().with_github_token(github_token, github);
+        if let Some(content) = gh_hosts_file().and_then(|p| std::fs::read_to_string(p).ok()) {
+            for (host, token) in parse_gh_hosts(&content) {
+                credentials.insert_default(&host, Credential::Token(token.clone()));
+                // the API of github.com is on its own host, GitHub Enterprise serves it under /api
+                if host == "github.com" {
+                    credentials.insert_default("api.github.com", Credential::Token(token));
+                }
+            }
+        }
+        if let Some(content) = netrc_file().and_then(|p| std::fs::read_to_string(p).ok()) {
+            for (host, credential) in parse_netrc(&content) {
+                credentials.insert_default(&host, credential);
+            }
+        }
+        debug!(
+            "Loaded credentials for {:?}",
+            credentials.hosts.keys().collect::<Vec<_>>()
+        );
+        credentials
+    }
+
+    /// Returns these credentials with a GitHub token for github.com, its API and the configured
+    /// GitHub Enterprise instances, if given
+    pub fn with_github_token(
+        mut self,
+        github_token: Option<String>,
+        github: &GithubConfig,
+    ) -> Self {
+        if let Some(token) = github_token.filter(|t| !t.is_empty()) {
+            let hosts = GITHUB_HOSTS.map(String::from).into_iter();
+            for host in hosts.chain(configured_hosts(github)) {
+                self.insert(&host, Credential::Token(token.clone()));
+            }
+        }
+        self
+    }
+
+    pub fn insert(&mut self, host: &str, credential: Credential) {
+        self.hosts.insert(host.to_lowercase(), credential);
+    }
+
+    fn insert_default(&mut self, host: &str, credential: Credential) {
+        self.hosts.entry(host.to_lowercase()).or_insert(credential);
+    }
+
+    /// Returns the credential for the host of `url`, if there is one
+    pub fn for_url(&self, url: &str) -> Option<&Credential> {
+        let url = reqwest::Url::parse(url).ok()?;
+        self.hosts.get(&url.host_str()?.to_lowercase())
+    }
+
+    /// Returns the `Authorization` header to send to `url`, if any
+    pub fn authorization(&self, url: &str) -> Option<String> {
+        self.for_url(url).map(Credential::authorization)
+    }
+}
+
+/// Returns the GitHub CLI hosts file: `$GH_CONFIG_DIR/hosts.yml`, or `gh/hosts.yml` in the config
+/// folder (`GitHub CLI` in `%AppData%` on Windows)
+fn gh_hosts_file() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("GH_CONFIG_DIR") {
+        return Some(PathBuf::from(dir).join("hosts.yml"));
+    }
+    #[cfg(windows)]
+    let dir = dirs::config_dir()?.join("GitHub CLI");
+    #[cfg(not(windows))]
+    let dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))?
+        .join("gh");
+    Some(dir.join("hosts.yml"))
+}
+
+/// Returns the netrc file: `$NETRC`, or `.netrc` (`_netrc` on Windows) in the home folder
+fn netrc_file() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("NETRC") {
+        return Some(PathBuf::from(path));
+    }
+    #[cfg(windows)]
+    let name = "_netrc";
+    #[cfg(not(windows))]
+    let name = ".netrc";
+    Some(dirs::home_dir()?.join(name))
+}
+
+#[derive(Deserialize)]
+struct GhHost {
+    oauth_token: Option<String>,
+}
+
+/// Returns the hosts and tokens of a GitHub CLI `hosts.yml`. Tokens kept in the system keyring
+/// are not in the file and are skipped.
+fn parse_gh_hosts(content: &str) -> Vec<(String, String)> {
+    let hosts: HashMap<String, GhHost> = serde_yaml::from_str(content).unwrap_or_default();
+    hosts
+        .into_iter()
+        .filter_map(|(host, entry)| Some((host, entry.oauth_token.filter(|t| !t.is_empty())?)))
+        .collect()
+}
+
+/// Returns the hosts and credentials of a netrc file. The `default` entry is ignored, so that
+/// credentials are never sent to hosts that are not listed explicitly.
+fn parse_netrc(content: &str) -> Vec<(String, Credential)> {
+    let mut entries = vec![];
+    let mut machine: Option<String> = None;
+    let mut login: Option<String> = None;
+    let mut password: Option<String> = None;
+    let mut flush = |machine: &mut Option<String>,
+                     login: &mut Option<String>,
+                     password: &mut Option<String>| {
+        if let (Some(host), Some(password)) = (machine.take(), password.take()) {
+            let credential = match login.take() {
+                Some(login) => Credential::Basic { login, password },
+                None => Credential::Token(password),
+            };
+            entries.push((host, credential));
+        }
+        *login = None;
+    };
+
+    // comments and macro definitions, which end with an empty line, are not credentials
+    let mut in_macdef = false;
+    let mut tokens = content
+        .lines()
+        .filter(|line| {
+            let line = line.trim_start();
+            if in_macdef {
+                in_macdef = !line.is_empty();
+                return false;
+            }
+            in_macdef = line.starts_with("macdef");
+            !in_macdef && !line.starts_with('#')
+        })
+        .flat_map(str::split_whitespace);
+    while let Some(token) = tokens.next() {
+        match token {
+            "machine" => {
+                flush(&mut machine, &mut login, &mut password);
+                machine = tokens.next().map(String::from);
+            }
+            "default" => {
+                flush(&mut machine, &mut login, &mut password);
+            }
+            "login" => login = tokens.next().map(String::from),
+            "password" => password = tokens.next().map(String::from),
+            _ => {}
+        }
+    }
+    flush(&mut machine, &mut login, &mut password);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credentials_for_exact_host() {
+        let credentials = Credentials::default()
+            .with_github_token(Some("ghp_123".to_string()), &GithubConfig::default());
+        assert_eq!(
+            credentials
+                .authorization("https://api.github.com/repos/MystenLabs/sui/releases")
+                .as_deref(),
+            Some("token ghp_123")
+        );
+        assert!(credentials
+            .authorization("https://github.com/MystenLabs/sui/releases/download/a.tgz")
+            .is_some());
+        // no substring matching, and nothing for the hosts downloads are redirected to
+        assert!(credentials
+            .authorization("https://github.com.example.com/a.tgz")
+            .is_none());
+        assert!(credentials
+            .authorization("https://objects.githubusercontent.com/a.tgz")
+            .is_none());
+        assert!(credentials
+            .authorization("https://storage.googleapis.com/mysten-walrus-binaries/a.tgz")
+            .is_none());
+    }
+
+    #[test]
+    fn test_parse_gh_hosts() {
+        let hosts = parse_gh_hosts(
+            r#"
+github.com:
+    user: octocat
+    oauth_token: gho_abc
+    git_protocol: https
+ghe.example.com:
+    user: octocat
+    git_protocol: ssh
+"#,
+        );
+        assert_eq!(
+            hosts,
+            vec![("github.com".to_string(), "gho_abc".to_string())]
+        );
+        assert!(parse_gh_hosts("not: [valid").is_empty());
+    }
+
+    #[test]
+    fn test_parse_netrc() {
+        let entries = parse_netrc(
+            "# mirrors\nmachine mirror.example.com login ci password s3cret\n\
+             macdef init\nmachine ignored.example.com password x\n\n\
+             machine api.github.com password ghp_456\n\
+             default login anonymous password guest\n",
+        );
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "mirror.example.com".to_string(),
+                    Credential::Basic {
+                        login: "ci".to_string(),
+                        password: "s3cret".to_string()
+                    }
+                ),
+                (
+                    "api.github.com".to_string(),
+                    Credential::Token("ghp_456".to_string())
+                ),
+            ]
+        );
+        assert_eq!(
+            entries[0].1.authorization(),
+            format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode("ci:s3cret")
+            )
+        );
+    }
+}
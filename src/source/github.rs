@@ -0,0 +1,523 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Error};
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use reqwest::header::{HeaderMap, ETAG, IF_NONE_MATCH, LINK};
+use reqwest::{RequestBuilder, StatusCode};
+
+use super::{open_url_as, send, AssetDownload, ReleaseSource, Resume};
+use crate::config::GithubConfig;
+use crate::options::Options;
+use crate::paths::get_suiup_cache_dir;
+use crate::types::{Asset, Release, Repo};
+
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+const DEFAULT_WEB_URL: &str = "https://github.com";
+
+/// Environment variable overriding the GitHub API URL of all binaries, e.g. to use a local
+/// stand-in server in tests
+pub const API_URL_ENV: &str = "SUIUP_GITHUB_API_URL";
+
+/// Environment variable overriding the GitHub web URL of all binaries
+pub const WEB_URL_ENV: &str = "SUIUP_GITHUB_WEB_URL";
+
+const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Maximum page size of the GitHub releases API
+const PER_PAGE: usize = 100;
+
+/// Releases published on GitHub. Release listings are cached together with their ETag.
+pub struct GithubSource {
+    options: Options,
+}
+
+impl GithubSource {
+    /// Creates a source authenticated with the credentials of the options
+    pub fn new(options: &Options) -> Self {
+        Self {
+            options: options.clone(),
+        }
+    }
+
+    fn get(&self, url: &str) -> RequestBuilder {
+        let request = self.options.client.get(url);
+        match self.options.credentials.authorization(url) {
+            Some(authorization) => request.header("Authorization", authorization),
+            None => request,
+        }
+    }
+
+    async fn download(
+        &self,
+        repo: &Repo,
+        asset: &Asset,
+        resume: Option<Resume>,
+    ) -> Result<AssetDownload, Error> {
+        // assets can be hosted elsewhere (e.g. walrus), the credentials are per host
+        let url = self.asset_url(repo, asset);
+        let accept = is_api_asset_url(&self.options.github, repo, &url).then_some(OCTET_STREAM);
+        let mut download = open_url_as(&self.options, &url, accept, resume).await?;
+        download.size = download.size.or(asset.size);
+        Ok(download)
+    }
+
+    /// Fetches all pages of the release list. With a cached list, pages are only fetched until
+    /// one contains a release that is already cached.
+    async fn fetch_releases(&self, repo: &Repo) -> Result<Vec<Release>, Error> {
+        let cached = load_cached_release_list(&self.options.github, repo)
+            .map_err(|e| anyhow!("Cannot load release list from cache: {e}"))?;
+        let release_url = format!(
+            "{}/repos/{}/releases?per_page={PER_PAGE}",
+            api_url(&self.options.github, repo),
+            repository(&self.options.github, repo)
+        );
+        let mut request = self.get(&release_url);
+
+        // Add ETag for caching
+        if cached.is_some() {
+            if let Ok(etag) = read_etag_file(&self.options.github, repo) {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+        }
+
+        let mut response = send(request, &self.options)
+            .await
+            .map_err(|e| anyhow!("Could not fetch releases of {repo}: {e}"))?;
+
+        // note this only works with authenticated requests. Should add support for that later.
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(releases) = cached {
+                return Ok(releases);
+            }
+        }
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+
+        let mut releases = vec![];
+        loop {
+            let next = next_page_url(response.headers());
+            let page: Vec<Release> = response.error_for_status()?.json().await?;
+            let reached_cache = cached.as_ref().is_some_and(|cached| {
+                page.iter().any(|r| {
+                    !r.tag_name.is_empty() && cached.iter().any(|c| c.tag_name == r.tag_name)
+                })
+            });
+            releases.extend(page);
+
+            match next {
+                Some(url) if !reached_cache => {
+                    response = send(self.get(&url), &self.options)
+                        .await
+                        .map_err(|e| anyhow!("Could not fetch releases of {repo}: {e}"))?;
+                }
+                _ => break,
+            }
+        }
+
+        if let Some(cached) = cached {
+            releases = merge_with_cache(releases, cached);
+        }
+        self.options.println("Saving releases list to cache");
+        save_release_list(&self.options.github, repo, &releases, etag)?;
+
+        Ok(releases)
+    }
+}
+
+impl ReleaseSource for GithubSource {
+    fn list_releases<'a>(&'a self, repo: &'a Repo) -> BoxFuture<'a, Result<Vec<Release>, Error>> {
+        self.fetch_releases(repo).boxed()
+    }
+
+    fn release_by_tag<'a>(
+        &'a self,
+        repo: &'a Repo,
+        tag: &'a str,
+    ) -> BoxFuture<'a, Result<Option<Release>, Error>> {
+        async move {
+            let url = format!(
+                "{}/repos/{}/releases/tags/{tag}",
+                api_url(&self.options.github, repo),
+                repository(&self.options.github, repo)
+            );
+            let response = send(self.get(&url), &self.options).await?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            Ok(Some(response.error_for_status()?.json().await?))
+        }
+        .boxed()
+    }
+
+    fn latest_release<'a>(&'a self, repo: &'a Repo) -> BoxFuture<'a, Result<Release, Error>> {
+        async move {
+            let url = format!(
+                "{}/repos/{}/releases/latest",
+                api_url(&self.options.github, repo),
+                repository(&self.options.github, repo)
+            );
+            let response = send(self.get(&url), &self.options).await?;
+            if !response.status().is_success() {
+                return Err(anyhow!(
+                    "Failed to fetch latest release of {repo} from GitHub"
+                ));
+            }
+            Ok(response.json().await?)
+        }
+        .boxed()
+    }
+
+    fn asset_url(&self, repo: &Repo, asset: &Asset) -> String {
+        match asset.id {
+            // the download URL of a private asset only works in a browser session
+            Some(id) if is_private(&self.options.github, repo) => format!(
+                "{}/repos/{}/releases/assets/{id}",
+                api_url(&self.options.github, repo),
+                repository(&self.options.github, repo)
+            ),
+            _ => asset.browser_download_url.clone(),
+        }
+    }
+
+    fn open_asset<'a>(
+        &'a self,
+        repo: &'a Repo,
+        asset: &'a Asset,
+    ) -> BoxFuture<'a, Result<AssetDownload, Error>> {
+        self.download(repo, asset, None).boxed()
+    }
+
+    fn resume_asset<'a>(
+        &'a self,
+        repo: &'a Repo,
+        asset: &'a Asset,
+        resume: Resume,
+    ) -> BoxFuture<'a, Result<AssetDownload, Error>> {
+        self.download(repo, asset, Some(resume)).boxed()
+    }
+}
+
+/// Returns a URL from the environment, or the one configured for the binary of a repository
+fn configured_url(
+    env: &str,
+    github: &GithubConfig,
+    repo: &Repo,
+    configured: impl Fn(&GithubConfig, &str) -> Option<String>,
+) -> Option<String> {
+    let url = std::env::var(env)
+        .ok()
+        .or_else(|| configured(github, repo.binary_name()))?;
+    Some(url.trim_end_matches('/').to_string())
+}
+
+/// Returns the base URL of the GitHub API serving the releases of a repository
+pub fn api_url(github: &GithubConfig, repo: &Repo) -> String {
+    configured_url(API_URL_ENV, github, repo, |c, binary| {
+        c.api_url(binary).map(String::from)
+    })
+    .unwrap_or_else(|| DEFAULT_API_URL.to_string())
+}
+
+/// Returns the base URL of the GitHub web interface hosting a repository. Without one configured,
+/// it is derived from a GitHub Enterprise API URL, which ends with `/api/v3`.
+pub fn web_url(github: &GithubConfig, repo: &Repo) -> String {
+    configured_url(WEB_URL_ENV, github, repo, |c, binary| {
+        c.web_url(binary).map(String::from)
+    })
+    .or_else(|| {
+        configured_url(API_URL_ENV, github, repo, |c, binary| {
+            c.api_url(binary).map(String::from)
+        })
+        .and_then(|api| api.strip_suffix("/api/v3").map(String::from))
+    })
+    .unwrap_or_else(|| DEFAULT_WEB_URL.to_string())
+}
+
+/// Returns the repository a binary is released from, `owner/name`. It can be overridden per
+/// binary, e.g. to install from a fork.
+pub fn repository(github: &GithubConfig, repo: &Repo) -> String {
+    github
+        .repository(repo.binary_name())
+        .map(String::from)
+        .unwrap_or_else(|| repo.to_string())
+}
+
+/// Whether the repository of a binary is configured as private
+fn is_private(github: &GithubConfig, repo: &Repo) -> bool {
+    github.is_private(repo.binary_name())
+}
+
+/// Whether a URL downloads an asset through the GitHub API, which only returns the content of
+/// the asset when asked for `application/octet-stream`
+fn is_api_asset_url(github: &GithubConfig, repo: &Repo, url: &str) -> bool {
+    url.strip_prefix(&api_url(github, repo))
+        .is_some_and(|path| path.starts_with("/repos/") && path.contains("/releases/assets/"))
+}
+
+/// Returns the hosts of the configured GitHub instances, which get the GitHub token
+pub(super) fn configured_hosts(github: &GithubConfig) -> Vec<String> {
+    let repos = [
+        Repo::Sui,
+        Repo::Mvr,
+        Repo::Walrus,
+        Repo::WalrusSites,
+        Repo::Suiup,
+    ];
+    let mut hosts: Vec<String> = repos
+        .iter()
+        .flat_map(|repo| [api_url(github, repo), web_url(github, repo)])
+        .filter_map(|url| Some(reqwest::Url::parse(&url).ok()?.host_str()?.to_lowercase()))
+        .collect();
+    hosts.sort();
+    hosts.dedup();
+    hosts
+}
+
+/// Returns the URL of the next page from a `Link` header, e.g.
+/// `<https://api.github.com/repositories/1/releases?page=2>; rel="next", <...>; rel="last"`
+fn next_page_url(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get(LINK)?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let mut params = part.split(';').map(str::trim);
+        let url = params.next()?.strip_prefix('<')?.strip_suffix('>')?;
+        params
+            .any(|param| param == r#"rel="next""#)
+            .then(|| url.to_string())
+    })
+}
+
+/// Merges freshly fetched releases, newest first, with the older releases from the cache
+fn merge_with_cache(fetched: Vec<Release>, cached: Vec<Release>) -> Vec<Release> {
+    let mut releases = fetched;
+    let older: Vec<Release> = cached
+        .into_iter()
+        .filter(|c| !releases.iter().any(|r| r.tag_name == c.tag_name))
+        .collect();
+    releases.extend(older);
+    releases
+}
+
+fn cache_file_names(github: &GithubConfig, repo: &Repo) -> (String, String) {
+    let mut repo_name = repository(github, repo).replace("/", "_");
+    // keep the releases of other GitHub instances apart
+    let api_url = api_url(github, repo);
+    if api_url != DEFAULT_API_URL {
+        let host = reqwest::Url::parse(&api_url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+            .unwrap_or_default();
+        repo_name = format!("{repo_name}_{}", host.replace(['.', ':'], "_"));
+    }
+    // release lists cached in `releases_*.txt` by older versions only have the first page
+    (
+        format!("etag_{}.txt", repo_name),
+        format!("releases_{}.json", repo_name),
+    )
+}
+
+fn read_etag_file(github: &GithubConfig, repo: &Repo) -> Result<String, Error> {
+    let (etag_filename, _) = cache_file_names(github, repo);
+    let etag_file = get_suiup_cache_dir().join(etag_filename);
+    if etag_file.exists() {
+        std::fs::read_to_string(&etag_file)
+            .map_err(|_| anyhow!("Cannot read from file {}", etag_file.display()))
+    } else {
+        Ok("".to_string())
+    }
+}
+
+fn save_release_list(
+    github: &GithubConfig,
+    repo: &Repo,
+    releases: &[Release],
+    etag: Option<String>,
+) -> Result<(), Error> {
+    let (etag_filename, releases_filename) = cache_file_names(github, repo);
+    let cache_dir = get_suiup_cache_dir();
+    std::fs::create_dir_all(&cache_dir).expect("Could not create cache directory");
+
+    let cache_file = cache_dir.join(releases_filename);
+    let etag_file = cache_dir.join(etag_filename);
+
+    let cache_content =
+        serde_json::to_string_pretty(releases).expect("Could not serialize releases file: {}");
+
+    write_atomically(&cache_file, &cache_content).map_err(|_| {
+        anyhow!(
+            "Could not write cache releases file: {}",
+            cache_file.display(),
+        )
+    })?;
+    if let Some(etag) = etag {
+        write_atomically(&etag_file, &etag)
+            .map_err(|_| anyhow!("Could not write ETag file: {}", etag_file.display()))?;
+    }
+    Ok(())
+}
+
+/// Writes a file through a temporary file renamed into place, so that another suiup process
+/// never reads it half written
+fn write_atomically(path: &Path, content: &str) -> Result<(), Error> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(content.as_bytes())?;
+    file.persist(path)?;
+    Ok(())
+}
+
+/// Returns the file the release list of a repository is cached in
+pub(super) fn cached_release_list_file(github: &GithubConfig, repo: &Repo) -> PathBuf {
+    let (_, releases_filename) = cache_file_names(github, repo);
+    get_suiup_cache_dir().join(releases_filename)
+}
+
+/// Reads a cached release list
+pub(super) fn read_cached_release_list(cache_file: &Path) -> Result<Vec<Release>, Error> {
+    serde_json::from_str(
+        &std::fs::read_to_string(cache_file)
+            .map_err(|_| anyhow!("Cannot read from file {}", cache_file.display()))?,
+    )
+    .map_err(|_| {
+        anyhow!(
+            "Cannot deserialize the releases cached file {}",
+            cache_file.display()
+        )
+    })
+}
+
+fn load_cached_release_list(
+    github: &GithubConfig,
+    repo: &Repo,
+) -> Result<Option<Vec<Release>>, Error> {
+    let (etag_filename, _) = cache_file_names(github, repo);
+    let cache_file = cached_release_list_file(github, repo);
+    let etag_file = get_suiup_cache_dir().join(etag_filename);
+
+    if cache_file.exists() && etag_file.exists() {
+        Ok(Some(read_cached_release_list(&cache_file)?))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn release(tag: &str) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            assets: vec![],
+        }
+    }
+
+    #[test]
+    fn test_next_page_url() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(next_page_url(&headers), None);
+
+        headers.insert(
+            LINK,
+            HeaderValue::from_static(
+                r#"<https://api.github.com/repositories/1/releases?per_page=100&page=1>; rel="prev", <https://api.github.com/repositories/1/releases?per_page=100&page=3>; rel="next", <https://api.github.com/repositories/1/releases?per_page=100&page=9>; rel="last""#,
+            ),
+        );
+        assert_eq!(
+            next_page_url(&headers).as_deref(),
+            Some("https://api.github.com/repositories/1/releases?per_page=100&page=3")
+        );
+
+        headers.insert(
+            LINK,
+            HeaderValue::from_static(
+                r#"<https://api.github.com/repositories/1/releases?page=1>; rel="first""#,
+            ),
+        );
+        assert_eq!(next_page_url(&headers), None);
+    }
+
+    #[test]
+    fn test_private_asset_url() {
+        let config: GithubConfig = toml::from_str(
+            r#"
+[suiup]
+repository = "our-org/suiup"
+private = true
+"#,
+        )
+        .unwrap();
+        let options = Options {
+            github: config,
+            ..Default::default()
+        };
+
+        let asset = Asset {
+            browser_download_url: "https://github.com/our-org/suiup/releases/download/v1/a.tgz"
+                .to_string(),
+            name: "a.tgz".to_string(),
+            id: Some(42),
+            ..Default::default()
+        };
+        let source = GithubSource::new(&options);
+        assert_eq!(repository(&options.github, &Repo::Suiup), "our-org/suiup");
+        let url = source.asset_url(&Repo::Suiup, &asset);
+        assert_eq!(
+            url,
+            "https://api.github.com/repos/our-org/suiup/releases/assets/42"
+        );
+        assert!(is_api_asset_url(&options.github, &Repo::Suiup, &url));
+        assert!(!is_api_asset_url(
+            &options.github,
+            &Repo::Suiup,
+            &asset.browser_download_url
+        ));
+
+        // assets of public repositories, or listed without an id, use their download URL
+        assert_eq!(repository(&options.github, &Repo::Sui), "MystenLabs/sui");
+        assert_eq!(
+            source.asset_url(&Repo::Sui, &asset),
+            asset.browser_download_url
+        );
+        let listed = Asset { id: None, ..asset };
+        assert_eq!(
+            source.asset_url(&Repo::Suiup, &listed),
+            listed.browser_download_url
+        );
+    }
+
+    #[test]
+    fn test_write_atomically() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file = temp_dir.path().join("releases_MystenLabs_sui.json");
+        write_atomically(&file, "[]").unwrap();
+        write_atomically(&file, "[{}]").unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "[{}]");
+        // no temporary file is left behind
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_merge_with_cache() {
+        let fetched = vec![release("testnet-v1.41.0"), release("testnet-v1.40.1")];
+        let cached = vec![release("testnet-v1.40.1"), release("testnet-v1.39.0")];
+        let tags: Vec<String> = merge_with_cache(fetched, cached)
+            .into_iter()
+            .map(|r| r.tag_name)
+            .collect();
+        assert_eq!(
+            tags,
+            vec!["testnet-v1.41.0", "testnet-v1.40.1", "testnet-v1.39.0"]
+        );
+    }
+}
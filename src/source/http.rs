@@ -0,0 +1,264 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The HTTP client shared by all requests, and sending requests with retries on transient
+//! failures and clear errors on rate limits
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Error};
+use reqwest::{
+    Certificate, Client, ClientBuilder, NoProxy, Proxy, RequestBuilder, Response, StatusCode,
+};
+use tracing::debug;
+
+use crate::config::HttpConfig;
+use crate::options::Options;
+
+/// Environment variable with a PEM file of additional CA certificates to trust
+const CA_BUNDLE_ENV: &str = "SUIUP_CA_BUNDLE";
+
+/// Environment variables with a proxy, picked up by the HTTP client itself
+const PROXY_ENV: [&str; 6] = [
+    "HTTPS_PROXY",
+    "https_proxy",
+    "HTTP_PROXY",
+    "http_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+];
+
+const USER_AGENT: &str = concat!("suiup/", env!("CARGO_PKG_VERSION"));
+
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn client_builder(config: &HttpConfig) -> ClientBuilder {
+    let seconds =
+        |timeout: Option<u64>, default| timeout.map(Duration::from_secs).unwrap_or(default);
+    Client::builder()
+        .user_agent(USER_AGENT)
+        .connect_timeout(seconds(config.connect_timeout, DEFAULT_CONNECT_TIMEOUT))
+        .read_timeout(seconds(config.read_timeout, DEFAULT_READ_TIMEOUT))
+}
+
+/// Builds an HTTP client from the settings, without looking at the environment
+fn build_client(config: &HttpConfig) -> Result<Client, Error> {
+    let mut builder = client_builder(config);
+    if let Some(proxy) = &config.proxy {
+        let proxy = Proxy::all(proxy)
+            .map_err(|e| anyhow!("Invalid proxy {proxy}: {e}"))?
+            .no_proxy(config.no_proxy.as_deref().and_then(NoProxy::from_string));
+        builder = builder.proxy(proxy);
+    }
+    if let Some(path) = &config.ca_bundle {
+        let pem = std::fs::read(path)
+            .map_err(|e| anyhow!("Cannot read the CA bundle {}: {e}", path.display()))?;
+        let certificates = Certificate::from_pem_bundle(&pem)
+            .map_err(|e| anyhow!("Cannot parse the CA bundle {}: {e}", path.display()))?;
+        if certificates.is_empty() {
+            bail!("The CA bundle {} contains no certificates", path.display());
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    builder
+        .build()
+        .map_err(|e| anyhow!("Cannot create the HTTP client: {e}"))
+}
+
+/// Creates the HTTP client shared by all requests from the settings, overridden by the
+/// environment
+pub fn http_client(config: &HttpConfig) -> Result<Client, Error> {
+    let mut config = config.clone();
+    if let Some(path) = std::env::var_os(CA_BUNDLE_ENV) {
+        config.ca_bundle = Some(PathBuf::from(path));
+    }
+    // the client uses the proxy of the environment, including its NO_PROXY, by default
+    if PROXY_ENV.iter().any(|var| std::env::var_os(var).is_some()) {
+        config.proxy = None;
+    }
+    if let Some(no_proxy) = ["NO_PROXY", "no_proxy"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok())
+    {
+        config.no_proxy = Some(no_proxy);
+    }
+
+    build_client(&config)
+}
+
+/// Maximum number of attempts of a request or a download
+pub(crate) const MAX_ATTEMPTS: u32 = 4;
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Longest `Retry-After` that is waited for, instead of failing right away
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Returns how long to wait before the next attempt, doubling with every attempt
+pub(crate) fn backoff(attempt: u32) -> Duration {
+    INITIAL_BACKOFF * 2u32.pow(attempt.saturating_sub(1))
+}
+
+fn header<T: FromStr>(response: &Response, name: &str) -> Option<T> {
+    response.headers().get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// Describes when a rate limit resets, e.g. `14:32 UTC (in 12 minutes)`
+fn describe_reset(reset: u64, now: u64) -> String {
+    let minutes = reset.saturating_sub(now).div_ceil(60);
+    let time_of_day = reset % 86400;
+    format!(
+        "{:02}:{:02} UTC (in {minutes} minutes)",
+        time_of_day / 3600,
+        time_of_day % 3600 / 60
+    )
+}
+
+/// Checks a response for rate limiting. Returns how long to wait before retrying, or an error if
+/// the limit is exhausted or resets too late to wait for it.
+fn check_rate_limit(response: &Response) -> Result<Option<Duration>, Error> {
+    if let Some(remaining) = header::<u64>(response, "x-ratelimit-remaining") {
+        debug!(
+            "{remaining} requests left in the rate limit of {}",
+            response.url()
+        );
+    }
+
+    let status = response.status();
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return Ok(None);
+    }
+
+    if header::<u64>(response, "x-ratelimit-remaining") == Some(0) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let until = header::<u64>(response, "x-ratelimit-reset")
+            .map(|reset| format!("until {}", describe_reset(reset, now)))
+            .unwrap_or_else(|| "for now".to_string());
+        bail!(
+            "GitHub API rate limit exceeded: rate limited {until}. Set GITHUB_TOKEN (or pass --github-token) to get a higher limit"
+        );
+    }
+
+    match header::<u64>(response, "retry-after").map(Duration::from_secs) {
+        Some(wait) if wait <= MAX_RETRY_AFTER => Ok(Some(wait)),
+        Some(wait) => bail!(
+            "Rate limited by {}, retry in {} seconds. Set GITHUB_TOKEN (or pass --github-token) to get a higher limit",
+            response.url().host_str().unwrap_or_default(),
+            wait.as_secs()
+        ),
+        // a 403 without rate limit headers is a permission error
+        None if status == StatusCode::FORBIDDEN => Ok(None),
+        None => Ok(Some(MAX_RETRY_AFTER)),
+    }
+}
+
+/// Whether a request error is worth retrying, e.g. a connection reset or a timeout
+fn is_transient(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout() || error.is_request()
+}
+
+/// Sends a request, retrying server errors, connection errors and short rate limits with
+/// exponential backoff. Exhausted rate limits are reported with the time they reset. With
+/// `best_effort` options the request is sent once.
+pub(crate) async fn send(request: RequestBuilder, options: &Options) -> Result<Response, Error> {
+    if options.best_effort {
+        return Ok(request.send().await?);
+    }
+
+    let mut attempt = 1;
+    loop {
+        let current = request
+            .try_clone()
+            .ok_or_else(|| anyhow!("Cannot retry a streaming request"))?;
+
+        let (wait, reason) = match current.send().await {
+            Ok(response) => {
+                let wait = match check_rate_limit(&response)? {
+                    Some(wait) => wait,
+                    None if response.status().is_server_error() => backoff(attempt),
+                    None => return Ok(response),
+                };
+                if attempt >= MAX_ATTEMPTS {
+                    return Ok(response);
+                }
+                (
+                    wait,
+                    format!("{} from {}", response.status(), response.url()),
+                )
+            }
+            Err(e) if is_transient(&e) && attempt < MAX_ATTEMPTS => {
+                (backoff(attempt), e.to_string())
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        options.println(format!(
+            "Request failed ({reason}), retrying in {:.1}s ({attempt}/{})...",
+            wait.as_secs_f32(),
+            MAX_ATTEMPTS - 1
+        ));
+        tokio::time::sleep(wait).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(backoff(1), Duration::from_millis(500));
+        assert_eq!(backoff(2), Duration::from_secs(1));
+        assert_eq!(backoff(4), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_describe_reset() {
+        // 2024-01-01 14:32:00 UTC
+        let reset = 1704119520;
+        assert_eq!(
+            describe_reset(reset, reset - 700),
+            "14:32 UTC (in 12 minutes)"
+        );
+        assert_eq!(
+            describe_reset(reset, reset + 10),
+            "14:32 UTC (in 0 minutes)"
+        );
+    }
+
+    #[test]
+    fn test_build_client() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = HttpConfig {
+            proxy: Some("http://proxy.example.com:3128".to_string()),
+            no_proxy: Some("localhost".to_string()),
+            connect_timeout: Some(5),
+            ..Default::default()
+        };
+        assert!(build_client(&config).is_ok());
+
+        let missing = HttpConfig {
+            ca_bundle: Some(temp_dir.path().join("missing.pem")),
+            ..Default::default()
+        };
+        assert!(build_client(&missing).is_err());
+
+        let empty = temp_dir.path().join("empty.pem");
+        std::fs::write(&empty, "").unwrap();
+        let empty = HttpConfig {
+            ca_bundle: Some(empty),
+            ..Default::default()
+        };
+        assert!(build_client(&empty)
+            .unwrap_err()
+            .to_string()
+            .contains("contains no certificates"));
+    }
+}
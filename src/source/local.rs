@@ -0,0 +1,137 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Error};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use futures_util::{stream, FutureExt, StreamExt};
+use tokio::io::AsyncReadExt;
+
+use super::{AssetDownload, ReleaseSource, RELEASES_FILE};
+use crate::types::{Asset, Release, Repo};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// A local directory holding `<repo>/releases.json` and `<repo>/<asset>`
+pub struct LocalDirSource {
+    root: PathBuf,
+}
+
+impl LocalDirSource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, repo: &Repo, file: &str) -> PathBuf {
+        self.root.join(repo.to_string()).join(file)
+    }
+}
+
+impl ReleaseSource for LocalDirSource {
+    fn list_releases<'a>(&'a self, repo: &'a Repo) -> BoxFuture<'a, Result<Vec<Release>, Error>> {
+        async move {
+            let path = self.path(repo, RELEASES_FILE);
+            let content = tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|e| anyhow!("Cannot read {}: {e}", path.display()))?;
+            serde_json::from_str(&content)
+                .map_err(|e| anyhow!("Cannot parse {}: {e}", path.display()))
+        }
+        .boxed()
+    }
+
+    fn asset_url(&self, repo: &Repo, asset: &Asset) -> String {
+        self.path(repo, &asset.name).display().to_string()
+    }
+
+    fn open_asset<'a>(
+        &'a self,
+        repo: &'a Repo,
+        asset: &'a Asset,
+    ) -> BoxFuture<'a, Result<AssetDownload, Error>> {
+        async move { open_file(&self.path(repo, &asset.name)).await }.boxed()
+    }
+}
+
+/// Opens a file as an asset download
+pub(super) async fn open_file(path: &Path) -> Result<AssetDownload, Error> {
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| anyhow!("Cannot open {}: {e}", path.display()))?;
+    let size = file.metadata().await?.len();
+
+    let stream = stream::try_unfold(file, |mut file| async move {
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        let n = file.read(&mut buffer).await?;
+        if n == 0 {
+            return Ok::<_, Error>(None);
+        }
+        buffer.truncate(n);
+        Ok(Some((Bytes::from(buffer), file)))
+    });
+
+    Ok(AssetDownload {
+        url: path.display().to_string(),
+        size: Some(size),
+        offset: 0,
+        validator: None,
+        stream: stream.boxed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::TryStreamExt;
+
+    #[tokio::test]
+    async fn test_local_dir_source() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo_dir = temp_dir.path().join("MystenLabs").join("sui");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::write(
+            repo_dir.join(RELEASES_FILE),
+            r#"[
+                {"tag_name": "testnet-v1.41.0", "assets": [{"name": "sui-testnet-v1.41.0-ubuntu-x86_64.tgz", "browser_download_url": "https://example.com/a"}]},
+                {"tag_name": "testnet-v1.40.1", "assets": []}
+            ]"#,
+        )
+        .unwrap();
+        let content = vec![7u8; CHUNK_SIZE + 10];
+        std::fs::write(
+            repo_dir.join("sui-testnet-v1.41.0-ubuntu-x86_64.tgz"),
+            &content,
+        )
+        .unwrap();
+
+        let source = LocalDirSource::new(temp_dir.path());
+        let releases = source.list_releases(&Repo::Sui).await.unwrap();
+        assert_eq!(releases.len(), 2);
+        assert_eq!(
+            source.latest_release(&Repo::Sui).await.unwrap().tag_name,
+            "testnet-v1.41.0"
+        );
+        assert!(source
+            .release_by_tag(&Repo::Sui, "testnet-v1.40.1")
+            .await
+            .unwrap()
+            .is_some());
+        assert!(source
+            .release_by_tag(&Repo::Sui, "testnet-v1.39.0")
+            .await
+            .unwrap()
+            .is_none());
+
+        let download = source
+            .open_asset(&Repo::Sui, &releases[0].assets[0])
+            .await
+            .unwrap();
+        assert_eq!(download.size, Some(content.len() as u64));
+        let chunks: Vec<Bytes> = download.stream.try_collect().await.unwrap();
+        assert_eq!(chunks.concat(), content);
+
+        assert!(source.list_releases(&Repo::Walrus).await.is_err());
+    }
+}
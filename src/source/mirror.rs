@@ -0,0 +1,91 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Error};
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+
+use super::{open_url, AssetDownload, ReleaseSource, Resume, RELEASES_FILE};
+use crate::options::Options;
+use crate::types::{Asset, Release, Repo};
+
+/// A static HTTP mirror serving `<repo>/releases.json` and `<repo>/<asset>` under a base URL
+pub struct HttpMirrorSource {
+    base_url: String,
+    options: Options,
+}
+
+impl HttpMirrorSource {
+    pub fn new(base_url: &str, options: &Options) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            options: options.clone(),
+        }
+    }
+
+    fn url(&self, repo: &Repo, file: &str) -> String {
+        format!("{}/{repo}/{file}", self.base_url)
+    }
+}
+
+impl ReleaseSource for HttpMirrorSource {
+    fn list_releases<'a>(&'a self, repo: &'a Repo) -> BoxFuture<'a, Result<Vec<Release>, Error>> {
+        async move {
+            let url = self.url(repo, RELEASES_FILE);
+            let content = open_url(&self.options, &url, None)
+                .await
+                .map_err(|e| anyhow!("Cannot fetch {url}: {e}"))?
+                .text()
+                .await?;
+            serde_json::from_str(&content).map_err(|e| anyhow!("Cannot parse {url}: {e}"))
+        }
+        .boxed()
+    }
+
+    fn asset_url(&self, repo: &Repo, asset: &Asset) -> String {
+        self.url(repo, &asset.name)
+    }
+
+    fn open_asset<'a>(
+        &'a self,
+        repo: &'a Repo,
+        asset: &'a Asset,
+    ) -> BoxFuture<'a, Result<AssetDownload, Error>> {
+        async move { open_url(&self.options, &self.asset_url(repo, asset), None).await }.boxed()
+    }
+
+    fn resume_asset<'a>(
+        &'a self,
+        repo: &'a Repo,
+        asset: &'a Asset,
+        resume: Resume,
+    ) -> BoxFuture<'a, Result<AssetDownload, Error>> {
+        async move { open_url(&self.options, &self.asset_url(repo, asset), Some(resume)).await }
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirror_urls() {
+        let source =
+            HttpMirrorSource::new("https://mirror.example.com/suiup/", &Options::default());
+        let asset = Asset {
+            browser_download_url: "https://github.com/MystenLabs/sui/releases/download/x.tgz"
+                .to_string(),
+            name: "sui-testnet-v1.40.1-ubuntu-x86_64.tgz".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            source.url(&Repo::Sui, RELEASES_FILE),
+            "https://mirror.example.com/suiup/MystenLabs/sui/releases.json"
+        );
+        assert_eq!(
+            source.asset_url(&Repo::Sui, &asset),
+            "https://mirror.example.com/suiup/MystenLabs/sui/sui-testnet-v1.40.1-ubuntu-x86_64.tgz"
+        );
+    }
+}
@@ -0,0 +1,340 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Release sources: where release listings and release assets are fetched from. GitHub is the
+//! default. A static HTTP mirror or a local directory can be used instead, laid out as
+//! `<repo>/releases.json` (in the format of the GitHub releases API) and `<repo>/<asset>`.
+
+mod credentials;
+mod github;
+mod http;
+mod local;
+mod mirror;
+mod offline;
+
+pub use credentials::{Credential, Credentials};
+pub use github::{api_url, repository, web_url, GithubSource, API_URL_ENV, WEB_URL_ENV};
+pub use http::http_client;
+pub(crate) use http::{backoff, send, MAX_ATTEMPTS};
+pub use local::LocalDirSource;
+pub use mirror::HttpMirrorSource;
+pub use offline::OfflineSource;
+
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Error};
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use futures_util::stream::BoxStream;
+use futures_util::{FutureExt, StreamExt, TryStreamExt};
+use reqwest::header::{
+    HeaderMap, HeaderValue, ACCEPT, ACCEPT_RANGES, AUTHORIZATION, CONTENT_RANGE, ETAG, IF_RANGE,
+    LAST_MODIFIED, RANGE,
+};
+use reqwest::StatusCode;
+
+use crate::options::Options;
+use crate::types::{Asset, Release, Repo};
+
+/// Name of the release listing of a repository in a mirror or a local directory
+pub const RELEASES_FILE: &str = "releases.json";
+
+/// A release asset being downloaded from a release source
+pub struct AssetDownload {
+    /// Where the asset is downloaded from
+    pub url: String,
+    /// Size of the whole asset, if known
+    pub size: Option<u64>,
+    /// Position in the asset the stream starts at: 0, unless a partial download is resumed
+    pub offset: u64,
+    /// Validator (`ETag` or `Last-Modified`) to resume the download with, if the source supports
+    /// range requests
+    pub validator: Option<String>,
+    pub stream: BoxStream<'static, Result<Bytes, Error>>,
+}
+
+/// A partial download to continue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resume {
+    /// Number of bytes already downloaded
+    pub offset: u64,
+    /// Validator of the partial download, sent as `If-Range` so that a changed asset is
+    /// downloaded in full
+    pub validator: String,
+}
+
+impl AssetDownload {
+    /// Reads the whole asset as text, for small assets such as checksum or signature files
+    pub async fn text(self) -> Result<String, Error> {
+        let chunks: Vec<Bytes> = self.stream.try_collect().await?;
+        String::from_utf8(chunks.concat()).map_err(|_| anyhow!("{} is not valid UTF-8", self.url))
+    }
+}
+
+/// Lists the releases of repositories and opens the downloads of their assets
+pub trait ReleaseSource: Send + Sync {
+    /// Lists the releases of a repository, newest first
+    fn list_releases<'a>(&'a self, repo: &'a Repo) -> BoxFuture<'a, Result<Vec<Release>, Error>>;
+
+    /// Returns the release with the given tag, if there is one
+    fn release_by_tag<'a>(
+        &'a self,
+        repo: &'a Repo,
+        tag: &'a str,
+    ) -> BoxFuture<'a, Result<Option<Release>, Error>> {
+        async move {
+            Ok(self
+                .list_releases(repo)
+                .await?
+                .into_iter()
+                .find(|r| r.tag_name == tag))
+        }
+        .boxed()
+    }
+
+    /// Returns the latest release of a repository
+    fn latest_release<'a>(&'a self, repo: &'a Repo) -> BoxFuture<'a, Result<Release, Error>> {
+        async move {
+            self.list_releases(repo)
+                .await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow!("No releases found for {repo}"))
+        }
+        .boxed()
+    }
+
+    /// Returns the location a release asset is downloaded from
+    fn asset_url(&self, repo: &Repo, asset: &Asset) -> String;
+
+    /// Opens the download of a release asset
+    fn open_asset<'a>(
+        &'a self,
+        repo: &'a Repo,
+        asset: &'a Asset,
+    ) -> BoxFuture<'a, Result<AssetDownload, Error>>;
+
+    /// Opens the download of a release asset from where a partial download stopped. Sources that
+    /// cannot resume downloads start over, with an offset of 0.
+    fn resume_asset<'a>(
+        &'a self,
+        repo: &'a Repo,
+        asset: &'a Asset,
+        _resume: Resume,
+    ) -> BoxFuture<'a, Result<AssetDownload, Error>> {
+        self.open_asset(repo, asset)
+    }
+}
+
+/// A mirror of the GitHub releases, laid out as `<repo>/releases.json` and `<repo>/<asset>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mirror {
+    Http(String),
+    Dir(PathBuf),
+}
+
+impl Mirror {
+    /// Parses a mirror location: an HTTP(S) URL, a `file://` URL or a directory
+    pub fn parse(location: &str) -> Result<Self, Error> {
+        match reqwest::Url::parse(location) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {
+                Ok(Self::Http(location.to_string()))
+            }
+            Ok(url) if url.scheme() == "file" => url
+                .to_file_path()
+                .map(Self::Dir)
+                .map_err(|_| anyhow!("Invalid mirror path: {location}")),
+            Ok(url) if url.scheme().len() > 1 => {
+                bail!("Unsupported mirror URL scheme `{}`", url.scheme())
+            }
+            // anything else, including Windows paths with a drive letter, is a path
+            _ => Ok(Self::Dir(PathBuf::from(location))),
+        }
+    }
+
+    pub fn source(&self, options: &Options) -> Box<dyn ReleaseSource> {
+        match self {
+            Self::Http(url) => Box::new(HttpMirrorSource::new(url, options)),
+            Self::Dir(path) => Box::new(LocalDirSource::new(path.clone())),
+        }
+    }
+}
+
+impl Display for Mirror {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Http(url) => write!(f, "{url}"),
+            Self::Dir(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Returns the release source to fetch releases and assets from: the mirror if one is set,
+/// otherwise GitHub. In offline mode, only a mirror directory or the caches are used.
+pub fn release_source(options: &Options) -> Box<dyn ReleaseSource> {
+    match options.mirror.clone() {
+        Some(Mirror::Http(_)) | None if options.offline => Box::new(OfflineSource::new(options)),
+        Some(mirror) => mirror.source(options),
+        None => Box::new(GithubSource::new(options)),
+    }
+}
+
+/// Opens an HTTP download, authenticated with the credential for the host of `url` if there is
+/// one. With `resume`, only the rest of the asset is requested, unless the server does not
+/// support range requests or the asset changed.
+pub(crate) async fn open_url(
+    options: &Options,
+    url: &str,
+    resume: Option<Resume>,
+) -> Result<AssetDownload, Error> {
+    open_url_as(options, url, None, resume).await
+}
+
+/// Opens an HTTP download like [`open_url`], asking for the `accept` media type if given
+pub(crate) async fn open_url_as(
+    options: &Options,
+    url: &str,
+    accept: Option<&str>,
+    resume: Option<Resume>,
+) -> Result<AssetDownload, Error> {
+    let authorization = options.credentials.authorization(url);
+    let get = || {
+        let mut request = options.client.get(url);
+        if let Some(authorization) = &authorization {
+            request = request.header(AUTHORIZATION, authorization);
+        }
+        if let Some(accept) = accept {
+            request = request.header(ACCEPT, accept);
+        }
+        request
+    };
+
+    let mut response = match &resume {
+        Some(resume) => {
+            send(
+                get()
+                    .header(RANGE, format!("bytes={}-", resume.offset))
+                    .header(IF_RANGE, &resume.validator),
+                options,
+            )
+            .await?
+        }
+        None => send(get(), options).await?,
+    };
+    // the partial download is larger than the asset, start over
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        response = send(get(), options).await?;
+    }
+    let response = response
+        .error_for_status()
+        .map_err(|e| anyhow!("Encountered unexpected error: {e}"))?;
+
+    let offset = match resume {
+        Some(resume) if response.status() == StatusCode::PARTIAL_CONTENT => resume.offset,
+        _ => 0,
+    };
+
+    //walrus is on google storage, so different content length header
+    let length = response.content_length().filter(|s| *s > 0).or_else(|| {
+        response
+            .headers()
+            .get("x-goog-stored-content-length")
+            .and_then(|c| c.to_str().ok())
+            .and_then(|c| c.parse::<u64>().ok())
+    });
+    let size = content_range_size(response.headers()).or(length.map(|l| offset + l));
+
+    Ok(AssetDownload {
+        url: url.to_string(),
+        size,
+        offset,
+        validator: range_validator(response.status(), response.headers()),
+        stream: response.bytes_stream().map_err(Error::from).boxed(),
+    })
+}
+
+/// Returns the size of the whole asset from a `Content-Range` header, e.g. `bytes 100-999/1000`
+fn content_range_size(headers: &HeaderMap) -> Option<u64> {
+    let range = headers.get(CONTENT_RANGE)?.to_str().ok()?;
+    range.rsplit_once('/')?.1.parse().ok()
+}
+
+/// Returns the validator to resume a download with, if the server supports range requests.
+/// Weak ETags cannot be used with `If-Range`, the `Last-Modified` date is used instead.
+fn range_validator(status: StatusCode, headers: &HeaderMap) -> Option<String> {
+    let accepts_ranges = status == StatusCode::PARTIAL_CONTENT
+        || headers
+            .get(ACCEPT_RANGES)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.eq_ignore_ascii_case("bytes"));
+    if !accepts_ranges {
+        return None;
+    }
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|v: &HeaderValue| v.to_str().ok())
+    };
+    header(ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(LAST_MODIFIED))
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mirror() {
+        assert_eq!(
+            Mirror::parse("https://mirror.example.com/suiup").unwrap(),
+            Mirror::Http("https://mirror.example.com/suiup".to_string())
+        );
+        assert_eq!(
+            Mirror::parse("./mirror").unwrap(),
+            Mirror::Dir(PathBuf::from("./mirror"))
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            Mirror::parse("file:///srv/suiup%20mirror").unwrap(),
+            Mirror::Dir(PathBuf::from("/srv/suiup mirror"))
+        );
+        assert!(Mirror::parse("ftp://mirror.example.com").is_err());
+    }
+
+    #[test]
+    fn test_range_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Wed, 21 Oct 2025 07:28:00 GMT"),
+        );
+        assert_eq!(range_validator(StatusCode::OK, &headers), None);
+
+        headers.insert(ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+        assert_eq!(
+            range_validator(StatusCode::OK, &headers).as_deref(),
+            Some("\"abc\"")
+        );
+
+        headers.insert(ETAG, HeaderValue::from_static("W/\"abc\""));
+        assert_eq!(
+            range_validator(StatusCode::OK, &headers).as_deref(),
+            Some("Wed, 21 Oct 2025 07:28:00 GMT")
+        );
+
+        headers.insert(ACCEPT_RANGES, HeaderValue::from_static("none"));
+        assert_eq!(range_validator(StatusCode::OK, &headers), None);
+        assert!(range_validator(StatusCode::PARTIAL_CONTENT, &headers).is_some());
+
+        assert_eq!(content_range_size(&headers), None);
+        headers.insert(
+            CONTENT_RANGE,
+            HeaderValue::from_static("bytes 100-999/1000"),
+        );
+        assert_eq!(content_range_size(&headers), Some(1000));
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Error};
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+
+use super::github::{cached_release_list_file, read_cached_release_list};
+use super::local::open_file;
+use super::{AssetDownload, ReleaseSource};
+use crate::config::GithubConfig;
+use crate::options::Options;
+use crate::paths::release_archive_dir;
+use crate::types::{Asset, Release, Repo};
+
+/// Serves releases from the cached release lists and assets from the release archives folder,
+/// without network access
+pub struct OfflineSource {
+    github: GithubConfig,
+}
+
+impl OfflineSource {
+    pub fn new(options: &Options) -> Self {
+        Self {
+            github: options.github.clone(),
+        }
+    }
+}
+
+impl ReleaseSource for OfflineSource {
+    fn list_releases<'a>(&'a self, repo: &'a Repo) -> BoxFuture<'a, Result<Vec<Release>, Error>> {
+        async move {
+            let cache_file = cached_release_list_file(&self.github, repo);
+            if !cache_file.exists() {
+                bail!(
+                    "The release list of {repo} is not cached ({} is missing). Run suiup once without --offline to cache it",
+                    cache_file.display()
+                );
+            }
+            read_cached_release_list(&cache_file)
+        }
+        .boxed()
+    }
+
+    fn asset_url(&self, _repo: &Repo, asset: &Asset) -> String {
+        release_archive_dir()
+            .join(&asset.name)
+            .display()
+            .to_string()
+    }
+
+    fn open_asset<'a>(
+        &'a self,
+        _repo: &'a Repo,
+        asset: &'a Asset,
+    ) -> BoxFuture<'a, Result<AssetDownload, Error>> {
+        async move {
+            let path = release_archive_dir().join(&asset.name);
+            if !path.exists() {
+                bail!(
+                    "{} is not in the release archives folder {} and cannot be downloaded in offline mode",
+                    asset.name,
+                    release_archive_dir().display()
+                );
+            }
+            open_file(&path).await
+        }
+        .boxed()
+    }
+}
@@ -23,6 +23,7 @@ pub enum Repo {
     Mvr,
     Walrus,
     WalrusSites,
+    Suiup,
 }
 
 impl Repo {
@@ -33,6 +34,7 @@ impl Repo {
             Repo::Sui => "sui",
             Repo::Walrus => "walrus",
             Repo::WalrusSites => "site-builder",
+            Repo::Suiup => "suiup",
         }
     }
 }
@@ -44,19 +46,29 @@ impl Display for Repo {
             Self::Sui => write!(f, "MystenLabs/sui"),
             Self::Walrus => write!(f, "MystenLabs/walrus"),
             Self::WalrusSites => write!(f, "MystenLabs/walrus-sites"),
+            Self::Suiup => write!(f, "MystenLabs/suiup"),
         }
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Release {
+    #[serde(default)]
+    pub tag_name: String,
     pub assets: Vec<Asset>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Asset {
     pub browser_download_url: String,
     pub name: String,
+    /// Id of the asset in the GitHub API, used to download assets of private repositories
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
 }
 
 pub struct Binaries {
@@ -86,6 +98,25 @@ pub struct BinaryVersion {
     pub debug: bool,
     /// Path to the binary
     pub path: Option<String>,
+    /// SHA-256 of the installed binary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// Release asset the binary was installed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+}
+
+/// The release asset an installed binary comes from
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Provenance {
+    /// Release tag, e.g. `testnet-v1.40.1`
+    pub tag: String,
+    /// Name of the downloaded asset
+    pub asset: String,
+    /// URL the asset was downloaded from
+    pub url: String,
+    /// SHA-256 of the downloaded asset (the archive, or the binary for standalone assets)
+    pub asset_sha256: String,
 }
 
 #[derive(
@@ -147,6 +178,11 @@ impl InstalledBinaries {
         self.binaries.retain(|b| b.binary_name != binary);
     }
 
+    /// Remove a single installed version of a binary from the installed binaries JSON file
+    pub fn remove_binary_version(&mut self, binary: &BinaryVersion) {
+        self.binaries.retain(|b| b != binary);
+    }
+
     /// List the binaries in the installed binaries JSON file
     pub fn binaries(&self) -> &[BinaryVersion] {
         &self.binaries
@@ -220,6 +256,8 @@ impl From<BTreeMap<String, (String, Version, bool)>> for Binaries {
                 version: v.1.to_string(),
                 debug: v.2,
                 path: None,
+                sha256: None,
+                provenance: None,
             })
             .collect();
         Binaries { binaries }
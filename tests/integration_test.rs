@@ -187,6 +187,57 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn test_install_debug_from_file() -> Result<()> {
+        let test_env = TestEnv::new()?;
+        test_env.initialize_paths()?;
+
+        // a release archive with a debug build, which is extracted as sui-debug-<version>
+        let archive = test_env
+            .temp_dir
+            .path()
+            .join("sui-testnet-v1.40.1-ubuntu-x86_64.tgz");
+        let script = b"#!/bin/sh\necho sui 1.40.1-5f1b3b7e\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(script.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            fs::File::create(&archive)?,
+            flate2::Compression::default(),
+        ));
+        builder.append_data(&mut header, "sui-debug", &script[..])?;
+        builder.into_inner()?.finish()?;
+
+        let mut cmd = suiup_command(
+            vec![
+                "install",
+                "sui",
+                "--from-file",
+                archive.to_str().unwrap(),
+                "--debug",
+                "-y",
+            ],
+            &test_env,
+        );
+        cmd.assert().success().stdout(predicate::str::contains(
+            "'sui-debug' extracted successfully!",
+        ));
+        assert!(test_env
+            .data_dir
+            .join("suiup/binaries/testnet/sui-debug-v1.40.1")
+            .exists());
+
+        // the SHA-256 recorded at install time is the one of the debug build
+        let mut cmd = suiup_command(vec!["verify"], &test_env);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("All binaries verified"));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_update_workflow() -> Result<()> {
         let test_env = TestEnv::new()?;